@@ -0,0 +1,522 @@
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui::{Color32, Pos2, Vec2, vec2};
+use rand::{Rng, seq::SliceRandom};
+use ulid::Ulid;
+
+use graphs::{Direction, Graph, GraphError, OrientedGraph, Result};
+
+use super::{
+    EdgeRoute, GraphDisplayer, LayoutTransition, Node,
+    jobs::{JobOutput, JobProgress},
+};
+
+pub fn copy_nodes(display: &mut GraphDisplayer) {
+    if !display.selected_nodes.is_empty() {
+        let mut id_map = HashMap::new();
+
+        // Copy all nodes in the temporary graph
+        let graph = &display.graphs[display.selected_graph];
+        display.temporary.nodes = display
+            .selected_nodes
+            .iter()
+            .filter_map(|id| {
+                let Some(node) = graph.nodes.get(id) else {
+                    display.toasts.error(GraphError::NodeNotFound(*id));
+                    return None;
+                };
+                // Create a new ID for the copied node
+                let nid = Ulid::new();
+                id_map.insert(*id, nid);
+                Some((nid, node.clone()))
+            })
+            .collect();
+
+        // Copy links between selected nodes
+        display.temporary.edges = display.graphs[display.selected_graph]
+            .edges
+            .clone()
+            .into_iter()
+            .filter_map(|(e, w)| {
+                id_map
+                    .get(&e.0)
+                    .cloned()
+                    .zip(id_map.get(&e.1).cloned())
+                    .zip(Some(w))
+            })
+            .collect();
+        display.temporary.edge_attributes = display.graphs[display.selected_graph]
+            .edge_attributes
+            .iter()
+            .filter_map(|(e, a)| {
+                id_map
+                    .get(&e.0)
+                    .cloned()
+                    .zip(id_map.get(&e.1).cloned())
+                    .zip(Some(a.clone()))
+            })
+            .collect();
+    }
+}
+
+pub fn cut_nodes(display: &mut GraphDisplayer) {
+    if !display.selected_nodes.is_empty() {
+        display.history.label("Cut nodes");
+
+        // Copy links between selected nodes
+        display.temporary.edges = display.graphs[display.selected_graph]
+            .edges
+            .clone()
+            .into_iter()
+            .filter(|(e, _)| {
+                display.selected_nodes.contains(&e.0) && display.selected_nodes.contains(&e.1)
+            })
+            .collect();
+        display.temporary.edge_attributes = display.graphs[display.selected_graph]
+            .edge_attributes
+            .clone()
+            .into_iter()
+            .filter(|(e, _)| {
+                display.selected_nodes.contains(&e.0) && display.selected_nodes.contains(&e.1)
+            })
+            .collect();
+
+        // Move all selected nodes to the temporary graph, removing the edges linking them to
+        // the rest of the graph
+        let graph = &mut display.graphs[display.selected_graph];
+        display.temporary.nodes = display
+            .selected_nodes
+            .drain()
+            .filter_map(|id| {
+                // No need to create a new ID since this node was cut, we reuse it
+                display.toasts.report(graph.remove(&id)).map(|n| (id, n))
+            })
+            .collect();
+        display
+            .selected_edges
+            .retain(|e| graph.edges.contains_key(e));
+    }
+}
+
+pub fn paste_nodes(display: &mut GraphDisplayer) {
+    println!("Graph to paste : {:?}", display.temporary);
+    display.history.label("Paste nodes");
+    // Clear selection
+    display.selected_nodes.clear();
+
+    // Move nodes
+    display.graphs[display.selected_graph]
+        .nodes
+        .extend(display.temporary.nodes.drain().map(|(i, mut n)| {
+            // Select the nodes
+            display.selected_nodes.insert(i);
+            // Slightly move all nodes, as to not appear on top of their originals (if placed in the same graph)
+            (i, {
+                n.pos += vec2(15.0, 15.0);
+                n
+            })
+        }));
+
+    // And copy edges
+    display.graphs[display.selected_graph]
+        .edges
+        .extend(display.temporary.edges.drain());
+    display.graphs[display.selected_graph]
+        .edge_attributes
+        .extend(display.temporary.edge_attributes.drain());
+}
+
+pub fn delete_nodes(display: &mut GraphDisplayer) {
+    display.history.label("Delete nodes");
+    for id in display.selected_nodes.drain() {
+        display
+            .toasts
+            .report(display.graphs[display.selected_graph].remove(&id));
+    }
+    display
+        .selected_edges
+        .retain(|e| display.graphs[display.selected_graph].edges.contains_key(e));
+    display.context_menu.visible = false;
+}
+
+pub fn delete_edges(display: &mut GraphDisplayer) {
+    display.history.label("Delete edges");
+    for edge in display.selected_edges.drain() {
+        display
+            .toasts
+            .report(display.graphs[display.selected_graph].unlink(&edge));
+    }
+    display.context_menu.visible = false;
+}
+
+/// Reverses the direction of the selected edges, swapping weights with the edges going the
+/// other way if there are some
+pub fn reverse_edges(display: &mut GraphDisplayer) {
+    display.history.label("Reverse edges");
+    let graph = &mut display.graphs[display.selected_graph];
+    let mut reversed = HashSet::new();
+
+    for (from, to) in display.selected_edges.drain() {
+        // Both directions of a pair may be selected, in which case they were already swapped
+        if reversed.contains(&(from, to)) {
+            continue;
+        }
+        if let Some(weight) = graph.edges.remove(&(from, to)) {
+            if let Some(other) = graph.edges.insert((to, from), weight) {
+                graph.edges.insert((from, to), other);
+            }
+
+            // Attributes follow their edge
+            let attributes = graph.edge_attributes.remove(&(from, to));
+            let other = graph.edge_attributes.remove(&(to, from));
+            if let Some(attributes) = attributes {
+                graph.edge_attributes.insert((to, from), attributes);
+            }
+            if let Some(other) = other {
+                graph.edge_attributes.insert((from, to), other);
+            }
+            reversed.insert((to, from));
+        }
+    }
+
+    display.selected_edges = reversed;
+    display.context_menu.visible = false;
+}
+
+pub fn link_nodes(display: &mut GraphDisplayer, all: bool, double: bool) {
+    display.history.label("Link nodes");
+    if all {
+        for n1 in display.selected_nodes.iter().by_ref() {
+            for n2 in display.selected_nodes.iter() {
+                let graph = &mut display.graphs[display.selected_graph];
+                display
+                    .toasts
+                    .report(graph.link(n1, n2, Default::default()));
+                if double {
+                    display
+                        .toasts
+                        .report(graph.link(n2, n1, Default::default()));
+                }
+            }
+        }
+    } else {
+        for n in display.selected_nodes.iter() {
+            let graph = &mut display.graphs[display.selected_graph];
+            let Some(pos) = graph.nodes.get(n).map(|node| node.pos) else {
+                display.toasts.error(GraphError::NodeNotFound(*n));
+                continue;
+            };
+            let mut closest = Pos2::new(f32::INFINITY, f32::INFINITY);
+            let mut target = None;
+            // Find closest node
+            for (id, data) in graph.nodes.iter() {
+                if *n != *id && data.pos.distance_sq(pos) < closest.distance_sq(pos) {
+                    closest = data.pos;
+                    target = Some(*id);
+                }
+            }
+            // There is no other node to link to
+            let Some(target) = target else {
+                display.toasts.error(GraphError::NotEnoughNodes {
+                    required: 2,
+                    found: graph.node_count(),
+                });
+                break;
+            };
+            // Link both nodes
+            display
+                .toasts
+                .report(graph.link(n, &target, Default::default()));
+            if double {
+                display
+                    .toasts
+                    .report(graph.link(&target, n, Default::default()));
+            }
+        }
+    }
+}
+
+/// Adds a graph as a new tab, and switches to it
+pub fn push_graph(display: &mut GraphDisplayer, graph: OrientedGraph<Node>) {
+    display.graphs.push(graph);
+    display.selected_graph = display.graphs.len() - 1;
+    display.selected_nodes.clear();
+    display.selected_edges.clear();
+}
+
+/// Opens a new tab with the graph built out of the current one by `transformation`, once it
+/// is done
+pub fn transform_graph(
+    display: &mut GraphDisplayer,
+    name: &str,
+    transformation: impl FnOnce(&OrientedGraph<Node>) -> OrientedGraph<Node> + Send + 'static,
+) {
+    spawn_job(display, name, |graph, _| {
+        Ok(JobOutput::NewGraph {
+            graph: transformation(&graph),
+            selection: HashSet::new(),
+        })
+    });
+}
+
+/// Opens a new tab where the selected nodes of the current graph are merged into one, placed
+/// at their center and named after them
+pub fn contract_selection(display: &mut GraphDisplayer) {
+    let nodes = display.selected_nodes.clone();
+
+    spawn_job(display, "Contract selection", move |graph, _| {
+        let mut selected: Vec<&Node> = nodes.iter().filter_map(|id| graph.nodes.get(id)).collect();
+        selected.sort_by(|a, b| a.name.cmp(&b.name));
+        let Some(first) = selected.first() else {
+            return Err(GraphError::NotEnoughNodes {
+                required: 1,
+                found: 0,
+            });
+        };
+
+        let center = selected
+            .iter()
+            .fold(Vec2::ZERO, |sum, n| sum + n.pos.to_vec2())
+            / selected.len() as f32;
+        let merged = Node {
+            name: selected
+                .iter()
+                .map(|n| n.name.as_str())
+                .collect::<Vec<_>>()
+                .join("+"),
+            pos: center.to_pos2(),
+            ..(*first).clone()
+        };
+
+        let (graph, id) = graph.contract(&nodes, merged)?;
+        Ok(JobOutput::NewGraph {
+            graph,
+            selection: HashSet::from([id]),
+        })
+    });
+}
+
+/// Runs `work` in the background, over a snapshot of the current graph
+pub fn spawn_job(
+    display: &mut GraphDisplayer,
+    name: &str,
+    work: impl FnOnce(OrientedGraph<Node>, &JobProgress) -> Result<JobOutput> + Send + 'static,
+) {
+    let graph = &display.graphs[display.selected_graph];
+    display
+        .jobs
+        .spawn(name, display.graph_revision(), graph, work);
+}
+
+/// Applies the outputs of the background jobs which are over
+pub fn apply_finished_jobs(display: &mut GraphDisplayer, time: f64) {
+    for (job, output) in display.jobs.take_finished() {
+        let Some(output) = output else {
+            display
+                .toasts
+                .error(format!("{} stopped unexpectedly", job.name));
+            continue;
+        };
+        let Some(output) = display.toasts.report(output) else {
+            continue;
+        };
+        if job.graph >= display.graphs.len() {
+            continue;
+        }
+
+        // Changes are shown, and recorded in the history, on the graph they apply to
+        let modifies = matches!(
+            output,
+            JobOutput::Selection(_)
+                | JobOutput::Colors(_)
+                | JobOutput::Positions(_)
+                | JobOutput::Routed { .. }
+        );
+        if modifies && job.graph != display.selected_graph {
+            // Changes made to the current graph are recorded before watching the job's one
+            display.history.watch(&display.graphs, job.graph);
+            display.selected_graph = job.graph;
+            display.selected_nodes.clear();
+            display.selected_edges.clear();
+        }
+
+        let graph = &mut display.graphs[job.graph];
+        match output {
+            JobOutput::Selection(nodes) => {
+                // The nodes may have been removed while the job was running
+                display.selected_nodes = nodes
+                    .into_iter()
+                    .filter(|id| graph.nodes.contains_key(id))
+                    .collect();
+            }
+            JobOutput::Colors(colors) => {
+                display.history.label(job.name);
+                for (id, color) in colors {
+                    if let Some(node) = graph.nodes.get_mut(&id) {
+                        node.color = color;
+                    }
+                }
+            }
+            JobOutput::Positions(positions) => animate_positions(display, positions, time),
+            JobOutput::Routed { positions, routes } => {
+                display.edge_routes.extend(routes);
+                animate_positions(display, positions, time);
+            }
+            JobOutput::Metrics(metrics) => {
+                display.inspector.metrics = Some(((job.graph, job.revision), metrics))
+            }
+            // Spectra of another matrix than the chosen one are outdated
+            JobOutput::Spectrum(kind, eigen) => {
+                if kind == display.inspector.matrix_kind {
+                    display.inspector.spectrum = Some(((job.graph, job.revision), eigen));
+                }
+            }
+            JobOutput::NewGraph { graph, selection } => {
+                push_graph(display, graph);
+                display.selected_nodes = selection;
+            }
+        }
+    }
+}
+
+/// Replaces the selection with the nodes returned by an algorithm run on the current graph
+pub fn select_result<F>(display: &mut GraphDisplayer, name: &str, algorithm: F)
+where
+    F: FnOnce(&OrientedGraph<Node>, &JobProgress) -> Result<HashSet<Ulid>> + Send + 'static,
+{
+    spawn_job(display, name, |graph, progress| {
+        Ok(JobOutput::Selection(algorithm(&graph, progress)?))
+    });
+    display.context_menu.visible = false;
+}
+
+/// Extends the selection to the nodes reachable from it, following the edges in `direction`
+pub fn select_reachable(display: &mut GraphDisplayer, direction: Direction) {
+    let starts: Vec<Ulid> = display.selected_nodes.iter().copied().collect();
+
+    select_result(display, "Select reachable", move |graph, _| {
+        let search = graph.bfs(starts.iter().copied(), direction, &mut ())?;
+        Ok(starts.into_iter().chain(search.order).collect())
+    });
+}
+
+/// Extends the selection to the biggest maximal clique containing it, if there is one
+pub fn select_maximal_clique(display: &mut GraphDisplayer) {
+    let selection = display.selected_nodes.clone();
+    select_result(display, "Maximal clique", move |graph, progress| {
+        Ok(graph
+            .maximal_cliques_with_progress(progress)?
+            .into_iter()
+            .filter(|c| c.is_superset(&selection))
+            .max_by_key(|c| c.len())
+            .unwrap_or(selection))
+    });
+}
+
+/// Applies the hierarchical layout to the current graph, storing the routes of the long edges
+pub fn apply_hierarchical_layout(display: &mut GraphDisplayer) {
+    let layout = display.hierarchical_layout.clone();
+    let route_edges = display.route_edges;
+
+    spawn_job(display, "Hierarchical layout", move |mut graph, _| {
+        let routes = layout.apply(&mut graph);
+        let routes = if route_edges {
+            routes
+                .into_iter()
+                .filter_map(|(edge, bends)| {
+                    let route = EdgeRoute {
+                        from: graph.nodes.get(&edge.0)?.pos,
+                        to: graph.nodes.get(&edge.1)?.pos,
+                        bends,
+                    };
+                    Some((edge, route))
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(JobOutput::Routed {
+            positions: graph.nodes.iter().map(|(id, n)| (*id, n.pos)).collect(),
+            routes,
+        })
+    });
+}
+
+/// Animates the nodes of the current graph towards the given positions
+pub fn animate_positions(display: &mut GraphDisplayer, target: HashMap<Ulid, Pos2>, time: f64) {
+    display.history.label("Layout");
+    let graph = &display.graphs[display.selected_graph];
+
+    display.transition = Some(LayoutTransition {
+        graph: display.selected_graph,
+        start: target
+            .keys()
+            .filter_map(|id| graph.nodes.get(id).map(|n| (*id, n.pos)))
+            .collect(),
+        target,
+        started: time,
+    });
+}
+
+pub fn undo(display: &mut GraphDisplayer) {
+    if let Some(graph) = display
+        .history
+        .undo(&mut display.graphs, display.selected_graph)
+    {
+        display.selected_graph = graph;
+        forget_removed_nodes(display);
+    }
+}
+
+pub fn redo(display: &mut GraphDisplayer) {
+    if let Some(graph) = display
+        .history
+        .redo(&mut display.graphs, display.selected_graph)
+    {
+        display.selected_graph = graph;
+        forget_removed_nodes(display);
+    }
+}
+
+/// Stops referring to nodes and edges which aren't in the current graph anymore
+fn forget_removed_nodes(display: &mut GraphDisplayer) {
+    let graph = &display.graphs[display.selected_graph];
+    display
+        .selected_nodes
+        .retain(|id| graph.nodes.contains_key(id));
+    display
+        .selected_edges
+        .retain(|e| graph.edges.contains_key(e));
+    display.transition = None;
+}
+
+/// Colors the nodes of the current graph so that linked nodes have different colors, using a
+/// random palette and visiting the nodes in a random order
+pub fn color_graph(display: &mut GraphDisplayer) {
+    spawn_job(display, "Color graph", |graph, progress| {
+        let mut generator = rand::rng();
+
+        let mut order: Vec<Ulid> = graph.nodes.keys().copied().collect();
+        order.shuffle(&mut generator);
+        let colors = graph.greedy_coloring_with_progress(order, progress)?;
+
+        let max_col = colors.values().copied().max().unwrap_or(0);
+        let palette: Vec<Color32> = (0..=max_col)
+            .map(|_| {
+                Color32::from_rgb(
+                    generator.random_range(0..=255),
+                    generator.random_range(0..=255),
+                    generator.random_range(0..=255),
+                )
+            })
+            .collect();
+
+        Ok(JobOutput::Colors(
+            colors
+                .into_iter()
+                .map(|(id, color)| (id, palette[color as usize]))
+                .collect(),
+        ))
+    });
+}
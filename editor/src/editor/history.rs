@@ -0,0 +1,356 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use ulid::Ulid;
+
+use super::Node;
+use graphs::{Attributes, OrientedGraph, Real};
+
+/// Maximum number of transactions that can be undone
+pub const MAX_HISTORY: usize = 200;
+
+/// Modification of a single element of a graph. `None` means the element doesn't exist
+#[derive(Debug, Clone)]
+pub enum Change {
+    Node {
+        id: Ulid,
+        before: Option<Node>,
+        after: Option<Node>,
+    },
+    Edge {
+        edge: (Ulid, Ulid),
+        before: Option<Real>,
+        after: Option<Real>,
+    },
+    EdgeAttributes {
+        edge: (Ulid, Ulid),
+        before: Option<Attributes>,
+        after: Option<Attributes>,
+    },
+}
+
+/// Group of changes made to a graph by a single operation
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub label: String,
+    pub graph: usize,
+    pub changes: Vec<Change>,
+}
+
+impl Transaction {
+    /// Computes the changes needed to go from `before` to `after`
+    fn diff(
+        label: String,
+        graph: usize,
+        before: &OrientedGraph<Node>,
+        after: &OrientedGraph<Node>,
+    ) -> Self {
+        let mut changes = Vec::new();
+
+        let nodes: HashSet<&Ulid> = before.nodes.keys().chain(after.nodes.keys()).collect();
+        for id in nodes {
+            let (b, a) = (before.nodes.get(id), after.nodes.get(id));
+            if b != a {
+                changes.push(Change::Node {
+                    id: *id,
+                    before: b.cloned(),
+                    after: a.cloned(),
+                });
+            }
+        }
+
+        let edges: HashSet<&(Ulid, Ulid)> = before.edges.keys().chain(after.edges.keys()).collect();
+        for edge in edges {
+            let (b, a) = (before.edges.get(edge), after.edges.get(edge));
+            if b != a {
+                changes.push(Change::Edge {
+                    edge: *edge,
+                    before: b.copied(),
+                    after: a.copied(),
+                });
+            }
+        }
+
+        let edges: HashSet<&(Ulid, Ulid)> = before
+            .edge_attributes
+            .keys()
+            .chain(after.edge_attributes.keys())
+            .collect();
+        for edge in edges {
+            let (b, a) = (
+                before.edge_attributes.get(edge),
+                after.edge_attributes.get(edge),
+            );
+            if b != a {
+                changes.push(Change::EdgeAttributes {
+                    edge: *edge,
+                    before: b.cloned(),
+                    after: a.cloned(),
+                });
+            }
+        }
+
+        Transaction {
+            label,
+            graph,
+            changes,
+        }
+    }
+
+    /// Puts the graph back in the state it was before the transaction
+    fn revert(&self, graph: &mut OrientedGraph<Node>) {
+        for change in self.changes.iter() {
+            match change {
+                Change::Node { id, before, .. } => set(&mut graph.nodes, *id, before.clone()),
+                Change::Edge { edge, before, .. } => set(&mut graph.edges, *edge, *before),
+                Change::EdgeAttributes { edge, before, .. } => {
+                    set(&mut graph.edge_attributes, *edge, before.clone())
+                }
+            }
+        }
+    }
+
+    /// Applies the transaction again
+    fn apply(&self, graph: &mut OrientedGraph<Node>) {
+        for change in self.changes.iter() {
+            match change {
+                Change::Node { id, after, .. } => set(&mut graph.nodes, *id, after.clone()),
+                Change::Edge { edge, after, .. } => set(&mut graph.edges, *edge, *after),
+                Change::EdgeAttributes { edge, after, .. } => {
+                    set(&mut graph.edge_attributes, *edge, after.clone())
+                }
+            }
+        }
+    }
+}
+
+/// Inserts or removes the value of the key
+fn set<K: Hash + Eq, V>(map: &mut HashMap<K, V>, key: K, value: Option<V>) {
+    match value {
+        Some(v) => map.insert(key, v),
+        None => map.remove(&key),
+    };
+}
+
+/// Undo/redo history of the graphs. Instead of having each operation describe its changes, the
+/// current graph is compared to a copy of it taken after the last recorded transaction, so that
+/// every modification is recorded. The comparison only happens once the graph may have changed
+#[derive(Debug, Default)]
+pub struct History {
+    pub undo: Vec<Transaction>,
+    pub redo: Vec<Transaction>,
+    /// Index and copy of the graph as of the last recorded transaction
+    checkpoint: Option<(usize, OrientedGraph<Node>)>,
+    /// Name of the next transaction
+    label: Option<String>,
+    /// Whether the watched graph may have changed since it was last compared to the checkpoint
+    dirty: bool,
+    /// Number of transactions recorded, undone or redone on each graph
+    revisions: HashMap<usize, u64>,
+}
+
+impl History {
+    /// Names the next transaction, if it hasn't been named already
+    pub fn label(&mut self, label: impl Into<String>) {
+        self.label.get_or_insert_with(|| label.into());
+        self.dirty = true;
+    }
+
+    /// Lets the history know that the watched graph may have been modified, for instance because
+    /// of some input
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Changes each time a transaction modifies the graph, so that results computed from it can
+    /// tell whether they are outdated
+    pub fn revision(&self, graph: usize) -> u64 {
+        self.revisions.get(&graph).copied().unwrap_or_default()
+    }
+
+    fn bump_revision(&mut self, graph: usize) {
+        *self.revisions.entry(graph).or_default() += 1;
+    }
+
+    /// Records the changes made to the graphs since the last transaction as a new one. While
+    /// `coalesce` is set (during a drag for example), changes are accumulated instead
+    pub fn record(&mut self, graphs: &[OrientedGraph<Node>], selected: usize, coalesce: bool) {
+        if coalesce {
+            return;
+        }
+
+        if self.dirty
+            && let Some((index, checkpoint)) = &self.checkpoint
+            && let Some(graph) = graphs.get(*index)
+        {
+            let transaction = Transaction::diff(
+                self.label.take().unwrap_or_else(|| "Edit".into()),
+                *index,
+                checkpoint,
+                graph,
+            );
+
+            if !transaction.changes.is_empty() {
+                let index = *index;
+                self.undo.push(transaction);
+                self.redo.clear();
+                if self.undo.len() > MAX_HISTORY {
+                    self.undo.remove(0);
+                }
+                self.checkpoint = Some((index, graph.clone()));
+                self.bump_revision(index);
+            }
+        }
+        self.dirty = false;
+
+        // The selected graph changed, watch the new one
+        if self.checkpoint.as_ref().is_none_or(|(i, _)| *i != selected) {
+            self.checkpoint = graphs.get(selected).map(|g| (selected, g.clone()));
+        }
+        self.label = None;
+    }
+
+    /// Records the pending changes of the watched graph, and watches `graph` from now on, so that
+    /// it can be modified before the next call to [`History::record`]
+    pub fn watch(&mut self, graphs: &[OrientedGraph<Node>], graph: usize) {
+        self.dirty = true;
+        self.record(graphs, graph, false);
+    }
+
+    /// Reverts the last transaction. Returns the index of the graph it modified
+    pub fn undo(&mut self, graphs: &mut [OrientedGraph<Node>], selected: usize) -> Option<usize> {
+        self.record(graphs, selected, false);
+
+        let transaction = self.undo.pop()?;
+        let graph = graphs.get_mut(transaction.graph)?;
+        transaction.revert(graph);
+
+        self.checkpoint = Some((transaction.graph, graph.clone()));
+        let index = transaction.graph;
+        self.bump_revision(index);
+        self.redo.push(transaction);
+        Some(index)
+    }
+
+    /// Applies again the last undone transaction. Returns the index of the graph it modified
+    pub fn redo(&mut self, graphs: &mut [OrientedGraph<Node>], selected: usize) -> Option<usize> {
+        self.record(graphs, selected, false);
+
+        let transaction = self.redo.pop()?;
+        let graph = graphs.get_mut(transaction.graph)?;
+        transaction.apply(graph);
+
+        self.checkpoint = Some((transaction.graph, graph.clone()));
+        let index = transaction.graph;
+        self.bump_revision(index);
+        self.undo.push(transaction);
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::pos2;
+    use graphs::{Graph, OrientedGraph, Real, Spatial};
+
+    use super::History;
+    use crate::editor::Node;
+
+    fn graph() -> Vec<OrientedGraph<Node>> {
+        vec![OrientedGraph::empty()]
+    }
+
+    #[test]
+    fn undo_and_redo_round_trip() {
+        let mut graphs = graph();
+        let mut history = History::default();
+        history.record(&graphs, 0, false);
+
+        history.label("Add nodes");
+        let a = graphs[0].insert(Node::at_pos(pos2(0.0, 0.0)));
+        let b = graphs[0].insert(Node::at_pos(pos2(1.0, 0.0)));
+        history.record(&graphs, 0, false);
+
+        history.label("Link nodes");
+        graphs[0].link(&a, &b, Real(2.0)).unwrap();
+        history.record(&graphs, 0, false);
+        assert_eq!(history.undo.len(), 2);
+        assert_eq!(history.undo[1].label, "Link nodes");
+
+        let linked = graphs[0].clone();
+        assert_eq!(history.undo(&mut graphs, 0), Some(0));
+        assert_eq!(graphs[0].edge_count(), 0);
+        assert_eq!(history.undo(&mut graphs, 0), Some(0));
+        assert_eq!(graphs[0].node_count(), 0);
+        assert_eq!(history.undo(&mut graphs, 0), None);
+
+        history.redo(&mut graphs, 0);
+        history.redo(&mut graphs, 0);
+        assert_eq!(graphs[0].nodes, linked.nodes);
+        assert_eq!(graphs[0].edges, linked.edges);
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn coalesced_changes_form_a_single_transaction() {
+        let mut graphs = graph();
+        let mut history = History::default();
+        let a = graphs[0].insert(Node::at_pos(pos2(0.0, 0.0)));
+        history.record(&graphs, 0, false);
+
+        history.label("Move nodes");
+        for x in 1..10 {
+            graphs[0].nodes.get_mut(&a).unwrap().pos.x = x as f32;
+            history.record(&graphs, 0, true);
+        }
+        history.record(&graphs, 0, false);
+        assert_eq!(history.undo.len(), 1);
+
+        history.undo(&mut graphs, 0);
+        assert_eq!(graphs[0].nodes[&a].pos.x, 0.0);
+    }
+
+    #[test]
+    fn revision_follows_transactions() {
+        let mut graphs = graph();
+        let mut history = History::default();
+        history.record(&graphs, 0, false);
+        assert_eq!(history.revision(0), 0);
+
+        // Nothing is compared until the graph is marked as modified
+        graphs[0].insert(Node::default());
+        history.record(&graphs, 0, false);
+        assert!(history.undo.is_empty());
+
+        history.mark_dirty();
+        history.record(&graphs, 0, false);
+        assert_eq!(history.undo.len(), 1);
+        assert_eq!(history.revision(0), 1);
+
+        history.undo(&mut graphs, 0);
+        assert_eq!(history.revision(0), 2);
+        assert_eq!(history.revision(1), 0);
+    }
+
+    #[test]
+    fn watched_graph_changes_are_recorded_on_it() {
+        let mut graphs = vec![OrientedGraph::empty(), OrientedGraph::empty()];
+        let mut history = History::default();
+        history.record(&graphs, 0, false);
+
+        history.label("Add node");
+        graphs[0].insert(Node::default());
+        history.watch(&graphs, 1);
+        history.label("Color graph");
+        let id = graphs[1].insert(Node::default());
+        history.record(&graphs, 1, false);
+
+        assert_eq!(history.undo.len(), 2);
+        assert_eq!(history.undo[0].graph, 0);
+        assert_eq!(history.undo[1].graph, 1);
+        assert_eq!(history.undo(&mut graphs, 1), Some(1));
+        assert!(!graphs[1].nodes.contains_key(&id));
+    }
+}
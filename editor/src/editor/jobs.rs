@@ -0,0 +1,142 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+};
+
+use eframe::egui::{Color32, Pos2};
+use ulid::Ulid;
+
+use graphs::{Eigen, GraphMetrics, MatrixKind, OrientedGraph, Progress, Result};
+
+use super::{EdgeRoute, Node};
+
+/// What a finished job changes in the editor
+#[derive(Debug)]
+pub enum JobOutput {
+    Selection(HashSet<Ulid>),
+    Colors(HashMap<Ulid, Color32>),
+    Positions(HashMap<Ulid, Pos2>),
+    /// Positions of a layout routing some of the edges through bend points
+    Routed {
+        positions: HashMap<Ulid, Pos2>,
+        routes: HashMap<(Ulid, Ulid), EdgeRoute>,
+    },
+    Metrics(GraphMetrics),
+    Spectrum(MatrixKind, Eigen),
+    /// Graph built out of the job's one, opened in a new tab with the given nodes selected
+    NewGraph {
+        graph: OrientedGraph<Node>,
+        selection: HashSet<Ulid>,
+    },
+}
+
+/// Shared between a job and the editor, for the job to report how far it got and to know
+/// whether it was cancelled
+#[derive(Debug, Clone, Default)]
+pub struct JobProgress {
+    /// Bits of the done fraction of the job, as a `f32`
+    fraction: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobProgress {
+    pub fn set(&self, fraction: f32) {
+        self.fraction.store(fraction.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.fraction.load(Ordering::Relaxed))
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Algorithms of the library report to the job running them, and stop once it is cancelled
+impl Progress for JobProgress {
+    fn report(&self, done: f32) -> bool {
+        self.set(done);
+        !self.is_cancelled()
+    }
+}
+
+/// Algorithm running on a worker thread, over a snapshot of one of the graphs
+#[derive(Debug)]
+pub struct Job {
+    pub name: String,
+    /// Index of the graph the job was started on, and to which its output applies
+    pub graph: usize,
+    /// Revision of the graph when the job was started
+    pub revision: u64,
+    pub progress: JobProgress,
+    output: Receiver<Result<JobOutput>>,
+}
+
+/// Jobs which haven't been applied yet
+#[derive(Debug, Default)]
+pub struct Jobs {
+    pub running: Vec<Job>,
+}
+
+impl Jobs {
+    /// Runs `work` on a worker thread, over a copy of `graph` as it is now
+    pub fn spawn(
+        &mut self,
+        name: impl Into<String>,
+        (index, revision): (usize, u64),
+        graph: &OrientedGraph<Node>,
+        work: impl FnOnce(OrientedGraph<Node>, &JobProgress) -> Result<JobOutput> + Send + 'static,
+    ) {
+        let (sender, output) = mpsc::channel();
+        let progress = JobProgress::default();
+        let snapshot = graph.clone();
+
+        let shared = progress.clone();
+        thread::spawn(move || {
+            let result = work(snapshot, &shared);
+            // Fails if the job was cancelled, as nobody waits for its output anymore
+            let _ = sender.send(result);
+        });
+
+        self.running.push(Job {
+            name: name.into(),
+            graph: index,
+            revision,
+            progress,
+            output,
+        });
+    }
+
+    /// Stops waiting for a job, letting it know that it can stop
+    pub fn cancel(&mut self, index: usize) {
+        let job = self.running.remove(index);
+        job.progress.cancel();
+    }
+
+    /// Removes the jobs which are over, returning their outputs. A job has none if its thread
+    /// stopped without sending it, because it panicked
+    pub fn take_finished(&mut self) -> Vec<(Job, Option<Result<JobOutput>>)> {
+        let mut finished = Vec::new();
+        let mut i = 0;
+
+        while i < self.running.len() {
+            match self.running[i].output.try_recv() {
+                Err(TryRecvError::Empty) => i += 1,
+                Ok(output) => finished.push((self.running.remove(i), Some(output))),
+                Err(TryRecvError::Disconnected) => finished.push((self.running.remove(i), None)),
+            }
+        }
+
+        finished
+    }
+}
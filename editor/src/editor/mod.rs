@@ -0,0 +1,239 @@
+pub mod actions;
+pub mod history;
+pub mod inputs;
+pub mod jobs;
+pub mod node;
+pub mod trace;
+
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui::{self, Context, Event, Pos2, Rect, Visuals, pos2, vec2};
+use history::History;
+use inputs::graph_keyboard_inputs;
+use jobs::Jobs;
+pub use node::*;
+use trace::TracePlayer;
+use ulid::Ulid;
+
+use graphs::{
+    Graph, MatrixKind, OrientedGraph, Traversal,
+    layout::{ForceLayout, HierarchicalLayout},
+};
+
+use crate::ui::{
+    self, context_menu::*, generator::GeneratorDialog, inspector::GraphInspector,
+    templates::TemplateParameters, toasts::Toasts, transformations::TransformParameters,
+};
+
+#[derive(Debug, PartialEq, Eq, Default)]
+pub enum GraphTools {
+    #[default]
+    Look,
+    Nodes,
+    Links,
+}
+
+/// Path of an edge going through bend points, valid as long as its ends don't move
+#[derive(Debug, Clone)]
+pub struct EdgeRoute {
+    pub from: Pos2,
+    pub to: Pos2,
+    pub bends: Vec<Pos2>,
+}
+
+impl EdgeRoute {
+    /// Points of the path, if its ends are still at the given positions
+    pub fn points(&self, from: Pos2, to: Pos2) -> Option<Vec<Pos2>> {
+        (self.from == from && self.to == to).then(|| {
+            std::iter::once(from)
+                .chain(self.bends.iter().copied())
+                .chain(std::iter::once(to))
+                .collect()
+        })
+    }
+}
+
+/// Duration of the animation between two layouts, in seconds
+pub const TRANSITION_DURATION: f64 = 0.5;
+
+/// Animation of the nodes of a graph, from their previous positions to new ones
+#[derive(Debug)]
+pub struct LayoutTransition {
+    pub graph: usize,
+    pub start: HashMap<Ulid, Pos2>,
+    pub target: HashMap<Ulid, Pos2>,
+    pub started: f64,
+}
+
+impl LayoutTransition {
+    /// Moves the nodes to where they should be at `time`. Returns true once the animation is over
+    pub fn apply(&self, graph: &mut OrientedGraph<Node>, time: f64) -> bool {
+        let t = ((time - self.started) / TRANSITION_DURATION).clamp(0.0, 1.0) as f32;
+        let eased = t * t * (3.0 - 2.0 * t);
+
+        for (id, target) in self.target.iter() {
+            if let (Some(node), Some(start)) = (graph.nodes.get_mut(id), self.start.get(id)) {
+                node.pos = start.lerp(*target, eased);
+            }
+        }
+
+        t >= 1.0
+    }
+}
+
+pub struct GraphDisplayer {
+    pub graphs: Vec<OrientedGraph<Node>>,
+    pub selected_graph: usize,
+    pub selected_nodes: HashSet<Ulid>,
+    pub selected_edges: HashSet<(Ulid, Ulid)>,
+    /// Source node of the link being drawn with the links tool, and the current end of the link
+    pub link_drag: Option<(Ulid, Pos2)>,
+    pub temporary: OrientedGraph<Node>,
+    pub rect: egui::Rect,
+    pub tool: GraphTools,
+    pub selection_rect: Rect,
+    pub context_menu: ContextMenu,
+    pub inspector: GraphInspector,
+    pub last_hovered_position: Pos2,
+    pub generator: GeneratorDialog,
+    pub template_parameters: TemplateParameters,
+    pub transform_parameters: TransformParameters,
+    pub force_layout: ForceLayout,
+    /// Whether the force-directed layout is continuously applied to the current graph
+    pub simulating: bool,
+    pub hierarchical_layout: HierarchicalLayout,
+    /// Whether the hierarchical layout routes long edges through its dummy nodes
+    pub route_edges: bool,
+    pub edge_routes: HashMap<(Ulid, Ulid), EdgeRoute>,
+    pub transition: Option<LayoutTransition>,
+    /// Whether the preset layouts only move the selected nodes
+    pub layout_selection_only: bool,
+    pub group_by_color: bool,
+    /// Root of the radial tree layout
+    pub layout_root: Option<Ulid>,
+    pub history: History,
+    pub show_history: bool,
+    /// Whether edge weights are drawn as labels on the edges
+    pub show_weights: bool,
+    /// Numeric edge attribute used as weight by the pathfinding, instead of the edge weights
+    pub weight_attribute: Option<String>,
+    /// Errors of the last operations
+    pub toasts: Toasts,
+    /// Algorithms running in the background
+    pub jobs: Jobs,
+    /// Traversal shown step by step
+    pub traversal: Traversal,
+    pub trace_player: Option<TracePlayer>,
+    /// Steps per second of the traversal replay
+    pub trace_speed: f32,
+}
+
+impl Default for GraphDisplayer {
+    fn default() -> Self {
+        Self {
+            graphs: vec![OrientedGraph::empty()],
+            selected_graph: 0,
+            rect: Rect::from_center_size(pos2(0.0, 0.0), vec2(1000.0, 1000.0)),
+            selected_nodes: HashSet::new(),
+            selected_edges: HashSet::new(),
+            link_drag: None,
+            temporary: OrientedGraph::empty(),
+            tool: GraphTools::Look,
+            selection_rect: Rect::ZERO,
+            context_menu: ContextMenu {
+                just_opened: false,
+                visible: false,
+                position: pos2(0.0, 0.0),
+            },
+            inspector: GraphInspector {
+                message: ":3".into(),
+                metrics: None,
+                matrix_kind: MatrixKind::Adjacency,
+                spectrum: None,
+                new_attribute: String::new(),
+            },
+            last_hovered_position: Pos2::default(),
+            generator: GeneratorDialog::default(),
+            template_parameters: TemplateParameters::default(),
+            transform_parameters: TransformParameters::default(),
+            force_layout: ForceLayout::default(),
+            simulating: false,
+            hierarchical_layout: HierarchicalLayout::default(),
+            route_edges: true,
+            edge_routes: HashMap::new(),
+            transition: None,
+            layout_selection_only: false,
+            group_by_color: false,
+            layout_root: None,
+            history: History::default(),
+            show_history: false,
+            show_weights: false,
+            weight_attribute: None,
+            toasts: Toasts::default(),
+            jobs: Jobs::default(),
+            traversal: Traversal::default(),
+            trace_player: None,
+            trace_speed: 4.0,
+        }
+    }
+}
+
+impl GraphDisplayer {
+    /// Index and revision of the current graph, telling which version of it results were
+    /// computed from
+    pub fn graph_revision(&self) -> (usize, u64) {
+        (
+            self.selected_graph,
+            self.history.revision(self.selected_graph),
+        )
+    }
+}
+
+impl eframe::App for GraphDisplayer {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        ctx.set_visuals(Visuals::dark());
+
+        let inputs = ctx.input(|i| i.clone());
+        actions::apply_finished_jobs(self, inputs.time);
+
+        // Register & apply keyboard inputs, unless they're meant for a text field
+        if !ctx.wants_keyboard_input() {
+            graph_keyboard_inputs(self, &inputs);
+        }
+
+        // Show side-panel inspector
+        ui::inspector::show_graph_inspector(ctx, self);
+        if self.show_history {
+            ui::history::show_history_panel(ctx, self);
+        }
+
+        // Show miscellaneous graph tools selection
+        ui::misc::show_graph_selector(ctx, self);
+        ui::misc::show_graph_tools(ctx, &mut self.tool);
+        ui::generator::show_generator_dialog(ctx, self);
+
+        // Plot the actual graph onto the frame
+        ui::plot::plot_graph(ctx, &inputs, self);
+
+        // Show the context (right-click) menu
+        show_context_menu(self, ctx);
+        ui::jobs::show_jobs(ctx, self);
+        self.toasts.show(ctx);
+
+        // Record this frame's modifications, merging those of continuous interactions. Frames
+        // without any input or animation can't have modified the graph
+        let coalesce = inputs.pointer.any_down()
+            || ctx.wants_keyboard_input()
+            || self.transition.is_some()
+            || self.simulating;
+        let input = inputs
+            .events
+            .iter()
+            .any(|e| !matches!(e, Event::PointerMoved(_) | Event::MouseMoved(_)));
+        if coalesce || input {
+            self.history.mark_dirty();
+        }
+        self.history
+            .record(&self.graphs, self.selected_graph, coalesce);
+    }
+}
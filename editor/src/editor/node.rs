@@ -0,0 +1,116 @@
+use std::f32::consts::SQRT_2;
+
+use eframe::egui::{Color32, Pos2, Stroke, Vec2};
+
+use graphs::{Attributes, Spatial, layout::POINT_RADIUS};
+
+/// Shape in which a node is drawn
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NodeShape {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+}
+
+impl NodeShape {
+    pub const ALL: [NodeShape; 3] = [NodeShape::Circle, NodeShape::Square, NodeShape::Diamond];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeShape::Circle => "Circle",
+            NodeShape::Square => "Square",
+            NodeShape::Diamond => "Diamond",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub color: Color32,
+    pub pos: Pos2,
+    pub shape: NodeShape,
+    /// Half the width of the node
+    pub radius: f32,
+    pub border: Stroke,
+    pub attributes: Attributes,
+}
+
+impl Default for Node {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            color: Color32::default(),
+            pos: Pos2::default(),
+            shape: NodeShape::default(),
+            radius: POINT_RADIUS,
+            border: Stroke::NONE,
+            attributes: Attributes::new(),
+        }
+    }
+}
+
+impl Spatial for Node {
+    fn pos(&self) -> Pos2 {
+        self.pos
+    }
+
+    fn set_pos(&mut self, pos: Pos2) {
+        self.pos = pos;
+    }
+
+    fn at_pos(pos: Pos2) -> Self {
+        Self {
+            name: "Nowode :3".into(),
+            color: Color32::GRAY,
+            pos,
+            ..Default::default()
+        }
+    }
+}
+
+impl Node {
+    pub fn new(pos: Pos2, color: Color32, name: impl Into<String>) -> Self {
+        Self {
+            color,
+            pos,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Corners of the node's outline, or `None` for a circle
+    pub fn corners(&self) -> Option<[Pos2; 4]> {
+        let (p, r) = (self.pos, self.radius);
+        match self.shape {
+            NodeShape::Circle => None,
+            NodeShape::Square => Some([
+                p + Vec2::new(-r, -r),
+                p + Vec2::new(r, -r),
+                p + Vec2::new(r, r),
+                p + Vec2::new(-r, r),
+            ]),
+            NodeShape::Diamond => {
+                // Same area as the square
+                let r = r * SQRT_2;
+                Some([
+                    p + Vec2::new(0.0, -r),
+                    p + Vec2::new(r, 0.0),
+                    p + Vec2::new(0.0, r),
+                    p + Vec2::new(-r, 0.0),
+                ])
+            }
+        }
+    }
+
+    /// Whether the position is inside the node's outline
+    pub fn contains(&self, pos: Pos2) -> bool {
+        let d = pos - self.pos;
+        match self.shape {
+            NodeShape::Circle => d.length() <= self.radius,
+            NodeShape::Square => d.x.abs().max(d.y.abs()) <= self.radius,
+            NodeShape::Diamond => d.x.abs() + d.y.abs() <= self.radius * SQRT_2,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use graphs::{TraceEvent, TraceState, Traversal};
+
+/// Replay of the trace of a traversal, one event at a time
+#[derive(Debug)]
+pub struct TracePlayer {
+    pub traversal: Traversal,
+    /// Index of the graph the trace was recorded on
+    pub graph: usize,
+    pub events: Vec<TraceEvent>,
+    /// Number of events already replayed
+    pub step: usize,
+    pub playing: bool,
+    /// Time of the last step taken while playing
+    last_step: f64,
+}
+
+impl TracePlayer {
+    pub fn new(traversal: Traversal, graph: usize, events: Vec<TraceEvent>) -> Self {
+        Self {
+            traversal,
+            graph,
+            events,
+            step: 0,
+            playing: false,
+            last_step: 0.0,
+        }
+    }
+
+    pub fn state(&self) -> TraceState {
+        TraceState::replay(&self.events[..self.step])
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.step >= self.events.len()
+    }
+
+    pub fn forward(&mut self) {
+        self.step = (self.step + 1).min(self.events.len());
+    }
+
+    pub fn back(&mut self) {
+        self.step = self.step.saturating_sub(1);
+    }
+
+    pub fn play(&mut self, time: f64) {
+        if self.is_over() {
+            self.step = 0;
+        }
+        self.playing = true;
+        self.last_step = time;
+    }
+
+    /// Takes the steps due at `time` while playing, at `speed` steps per second. Returns whether
+    /// it's still playing
+    pub fn advance(&mut self, time: f64, speed: f32) -> bool {
+        if !self.playing {
+            return false;
+        }
+
+        let interval = 1.0 / speed.max(0.01) as f64;
+        while time - self.last_step >= interval && !self.is_over() {
+            self.forward();
+            self.last_step += interval;
+        }
+
+        self.playing = !self.is_over();
+        self.playing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graphs::{OrientedGraph, Traversal};
+
+    use super::TracePlayer;
+    use crate::editor::Node;
+
+    #[test]
+    fn back_then_forward_is_the_identity() {
+        let graph: OrientedGraph<Node> = OrientedGraph::petersen();
+        let start = *graph.nodes.keys().next().unwrap();
+
+        for traversal in Traversal::ALL {
+            let events = graph.trace(traversal, &start).unwrap();
+            let mut player = TracePlayer::new(traversal, 0, events);
+
+            while !player.is_over() {
+                player.forward();
+                let (step, state) = (player.step, player.state());
+                player.back();
+                player.forward();
+                assert_eq!((player.step, player.state()), (step, state));
+            }
+
+            // Both ends of the trace are bounds
+            player.forward();
+            assert!(player.is_over());
+            player.step = 0;
+            player.back();
+            assert_eq!(player.step, 0);
+        }
+    }
+}
@@ -1,12 +1,11 @@
 use eframe::{NativeOptions, egui::pos2};
 
-use editor::GraphDisplayer;
+use editor::{GraphDisplayer, Node};
 
-use graphs::{Graph, Node, OrientedGraph};
+use graphs::{Graph, OrientedGraph, Real, Spatial};
 use ulid::Ulid;
 
 mod editor;
-pub(crate) mod graphs;
 pub mod ui;
 
 fn main() {
@@ -20,7 +19,9 @@ fn main() {
     for i in 0..4 {
         let cur_id = graph.insert(Node::at_pos(pos2(i as f32 * 50.0 - 100.0, 50.0 * i as f32)));
         if i > 0 {
-            graph.link(&prev_id, &cur_id, 0);
+            graph
+                .link(&prev_id, &cur_id, Real(0.0))
+                .expect("both nodes were just inserted");
         }
         prev_id = cur_id;
     }
@@ -1,9 +1,10 @@
-use eframe::egui::{self, Align2, Context, Layout, Pos2};
+use eframe::egui::{self, Align2, Context, Layout, Pos2, Ui};
+
+use graphs::{Direction, Graph, Spatial};
 
 use crate::{
     GraphDisplayer,
-    editor::{GraphTools, actions::*},
-    graphs::{Graph, Node},
+    editor::{GraphTools, Node, actions::*},
 };
 
 use super::widgets::action_label::ActionLabel;
@@ -35,7 +36,7 @@ pub fn show_context_menu(display: &mut GraphDisplayer, context: &Context) {
                     Layout::top_down_justified(egui::Align::LEFT),
                     |ui| match display.tool {
                         GraphTools::Look => {
-                            ui.label("WIP...");
+                            show_select_results(display, ui);
                         }
                         GraphTools::Nodes => {
                             let multi_enabled = !display.selected_nodes.is_empty();
@@ -43,6 +44,7 @@ pub fn show_context_menu(display: &mut GraphDisplayer, context: &Context) {
                             ui.label("Nodes");
                             ui.indent("node_actions", |ui| {
                                 if ui.add(ActionLabel::new("➕ Add", "N")).clicked() {
+                                    display.history.label("Add node");
                                     display.graphs[display.selected_graph]
                                         .insert(Node::at_pos(display.last_hovered_position));
                                     display.context_menu.visible = false;
@@ -89,8 +91,34 @@ pub fn show_context_menu(display: &mut GraphDisplayer, context: &Context) {
                                     delete_nodes(display);
                                 }
                             });
+
+                            ui.separator();
+
+                            show_select_results(display, ui);
+                        }
+                        GraphTools::Links => {
+                            let edges_enabled = !display.selected_edges.is_empty();
+
+                            ui.label("Links");
+                            ui.indent("link_actions", |ui| {
+                                if ui
+                                    .add_enabled(edges_enabled, ActionLabel::new("⇄ Reverse", "R"))
+                                    .clicked()
+                                {
+                                    reverse_edges(display);
+                                }
+                                if ui
+                                    .add_enabled(edges_enabled, ActionLabel::new("🗑Delete", "Del"))
+                                    .clicked()
+                                {
+                                    delete_edges(display);
+                                }
+                            });
+
+                            ui.separator();
+
+                            show_select_results(display, ui);
                         }
-                        _ => {}
                     },
                 );
             });
@@ -99,3 +127,63 @@ pub fn show_context_menu(display: &mut GraphDisplayer, context: &Context) {
         display.context_menu.just_opened = false;
     }
 }
+
+/// Actions replacing the selection with the result of an algorithm on the current graph
+fn show_select_results(display: &mut GraphDisplayer, ui: &mut Ui) {
+    ui.label("Select");
+    ui.indent("select_results", |ui| {
+        let selection = !display.selected_nodes.is_empty();
+        if ui
+            .add_enabled(selection, ActionLabel::new("Reachable from selection", ""))
+            .clicked()
+        {
+            select_reachable(display, Direction::Outgoing);
+        }
+        if ui
+            .add_enabled(selection, ActionLabel::new("Ancestors of selection", ""))
+            .clicked()
+        {
+            select_reachable(display, Direction::Incoming);
+        }
+        if ui.add(ActionLabel::new("Maximum clique", "")).clicked() {
+            select_result(display, "Maximum clique", |g, p| {
+                g.maximum_clique_with_progress(p)
+            });
+        }
+        if ui.add(ActionLabel::new("Maximal clique", "")).clicked() {
+            select_maximal_clique(display);
+        }
+        if ui
+            .add(ActionLabel::new("Maximum independent set", ""))
+            .clicked()
+        {
+            select_result(display, "Maximum independent set", |g, p| {
+                g.maximum_independent_set_with_progress(p)
+            });
+        }
+        if ui
+            .add(ActionLabel::new("Independent set (greedy)", ""))
+            .clicked()
+        {
+            select_result(display, "Independent set", |g, _| {
+                Ok(g.greedy_independent_set())
+            });
+        }
+        if ui
+            .add(ActionLabel::new("Minimum vertex cover", ""))
+            .clicked()
+        {
+            select_result(display, "Minimum vertex cover", |g, p| {
+                g.minimum_vertex_cover_with_progress(p)
+            });
+        }
+        if ui
+            .add(ActionLabel::new("Vertex cover (2-approx.)", ""))
+            .clicked()
+        {
+            select_result(display, "Vertex cover", |g, _| {
+                Ok(g.approximate_vertex_cover())
+            });
+        }
+    });
+}
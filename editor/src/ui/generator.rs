@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use eframe::egui::{self, Context, DragValue};
+use rand::{SeedableRng, rngs::StdRng};
+
+use graphs::{OrientedGraph, Progress, Result};
+
+use crate::editor::{GraphDisplayer, Node, actions::spawn_job, jobs::JobOutput};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum GeneratorKind {
+    #[default]
+    ErdosRenyiGnp,
+    ErdosRenyiGnm,
+    BarabasiAlbert,
+    WattsStrogatz,
+    RandomRegular,
+    RandomTree,
+    RandomGeometric,
+}
+
+impl GeneratorKind {
+    pub const ALL: [GeneratorKind; 7] = [
+        GeneratorKind::ErdosRenyiGnp,
+        GeneratorKind::ErdosRenyiGnm,
+        GeneratorKind::BarabasiAlbert,
+        GeneratorKind::WattsStrogatz,
+        GeneratorKind::RandomRegular,
+        GeneratorKind::RandomTree,
+        GeneratorKind::RandomGeometric,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GeneratorKind::ErdosRenyiGnp => "Erdős–Rényi G(n, p)",
+            GeneratorKind::ErdosRenyiGnm => "Erdős–Rényi G(n, m)",
+            GeneratorKind::BarabasiAlbert => "Barabási–Albert",
+            GeneratorKind::WattsStrogatz => "Watts–Strogatz",
+            GeneratorKind::RandomRegular => "Random regular",
+            GeneratorKind::RandomTree => "Random tree",
+            GeneratorKind::RandomGeometric => "Random geometric",
+        }
+    }
+}
+
+/// Parameters of the random graph generator window
+#[derive(Debug, Clone)]
+pub struct GeneratorDialog {
+    pub open: bool,
+    pub kind: GeneratorKind,
+    pub nodes: usize,
+    /// Edge probability for G(n, p), rewiring probability for Watts–Strogatz
+    pub probability: f64,
+    /// Edge count for G(n, m), edges per new node for Barabási–Albert
+    pub edges: usize,
+    /// Neighbor count for Watts–Strogatz, degree for random regular graphs
+    pub degree: usize,
+    pub radius: f32,
+    pub seed: u64,
+}
+
+impl Default for GeneratorDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            kind: GeneratorKind::default(),
+            nodes: 20,
+            probability: 0.2,
+            edges: 30,
+            degree: 4,
+            radius: 0.3,
+            seed: 0,
+        }
+    }
+}
+
+impl GeneratorDialog {
+    /// Generates the graph described by the parameters, if possible
+    pub fn generate(&self, progress: &impl Progress) -> Result<OrientedGraph<Node>> {
+        let rng = &mut StdRng::seed_from_u64(self.seed);
+        let n = self.nodes;
+
+        Ok(match self.kind {
+            GeneratorKind::ErdosRenyiGnp => {
+                OrientedGraph::erdos_renyi_gnp(n, self.probability, rng)
+            }
+            GeneratorKind::ErdosRenyiGnm => OrientedGraph::erdos_renyi_gnm(n, self.edges, rng),
+            GeneratorKind::BarabasiAlbert => OrientedGraph::barabasi_albert(n, self.edges, rng),
+            GeneratorKind::WattsStrogatz => {
+                OrientedGraph::watts_strogatz(n, self.degree, self.probability, rng)
+            }
+            GeneratorKind::RandomRegular => {
+                OrientedGraph::random_regular_with_progress(n, self.degree, rng, progress)?
+            }
+            GeneratorKind::RandomTree => OrientedGraph::random_tree(n, rng),
+            GeneratorKind::RandomGeometric => OrientedGraph::random_geometric(n, self.radius, rng),
+        })
+    }
+}
+
+pub fn show_generator_dialog(ctx: &Context, displayer: &mut GraphDisplayer) {
+    let dialog = &mut displayer.generator;
+    let mut open = dialog.open;
+    let mut generate = false;
+
+    egui::Window::new("Random graph")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::ComboBox::from_id_salt("generator_kind")
+                .selected_text(dialog.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in GeneratorKind::ALL {
+                        ui.selectable_value(&mut dialog.kind, kind, kind.name());
+                    }
+                });
+
+            egui::Grid::new("generator_parameters").show(ui, |ui| {
+                ui.label("Nodes");
+                ui.add(DragValue::new(&mut dialog.nodes).range(0..=2000));
+                ui.end_row();
+
+                // Bounds of a simple undirected graph on these nodes
+                let max_degree = dialog.nodes.saturating_sub(1);
+                let max_edges = dialog.nodes * max_degree / 2;
+
+                match dialog.kind {
+                    GeneratorKind::ErdosRenyiGnp => {
+                        ui.label("Edge probability");
+                        ui.add(
+                            DragValue::new(&mut dialog.probability)
+                                .range(0.0..=1.0)
+                                .speed(0.01),
+                        );
+                        ui.end_row();
+                    }
+                    GeneratorKind::ErdosRenyiGnm => {
+                        ui.label("Edges");
+                        ui.add(DragValue::new(&mut dialog.edges).range(0..=max_edges));
+                        ui.end_row();
+                    }
+                    GeneratorKind::BarabasiAlbert => {
+                        ui.label("Edges per node");
+                        ui.add(DragValue::new(&mut dialog.edges).range(1..=dialog.nodes.max(1)));
+                        ui.end_row();
+                    }
+                    GeneratorKind::WattsStrogatz => {
+                        ui.label("Neighbors");
+                        ui.add(DragValue::new(&mut dialog.degree).range(0..=max_degree));
+                        ui.end_row();
+                        ui.label("Rewiring probability");
+                        ui.add(
+                            DragValue::new(&mut dialog.probability)
+                                .range(0.0..=1.0)
+                                .speed(0.01),
+                        );
+                        ui.end_row();
+                    }
+                    GeneratorKind::RandomRegular => {
+                        ui.label("Degree");
+                        ui.add(DragValue::new(&mut dialog.degree).range(0..=max_degree));
+                        ui.end_row();
+                    }
+                    GeneratorKind::RandomTree => {}
+                    GeneratorKind::RandomGeometric => {
+                        ui.label("Radius");
+                        ui.add(
+                            DragValue::new(&mut dialog.radius)
+                                .range(0.0..=1.5)
+                                .speed(0.01),
+                        );
+                        ui.end_row();
+                    }
+                }
+
+                ui.label("Seed");
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut dialog.seed));
+                    if ui.button("🎲").clicked() {
+                        dialog.seed = rand::random();
+                    }
+                });
+                ui.end_row();
+            });
+
+            generate = ui.button("Generate").clicked();
+        });
+
+    displayer.generator.open = open;
+    if generate {
+        // Big graphs take a while to build, the current graph is left untouched
+        let params = displayer.generator.clone();
+        spawn_job(displayer, params.kind.name(), move |_, progress| {
+            Ok(JobOutput::NewGraph {
+                graph: params.generate(progress)?,
+                selection: HashSet::new(),
+            })
+        });
+    }
+}
@@ -0,0 +1,73 @@
+use eframe::egui::{self, Context, RichText};
+
+use crate::editor::{
+    GraphDisplayer,
+    actions::{redo, undo},
+};
+
+/// Lists the transactions of the history, from the oldest to the newest. Clicking one moves the
+/// history to the state right after it
+pub fn show_history_panel(ctx: &Context, displayer: &mut GraphDisplayer) {
+    egui::SidePanel::right("History")
+        .default_width(160.0)
+        .show(ctx, |ui| {
+            ui.heading("History");
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !displayer.history.undo.is_empty(),
+                        egui::Button::new("⮪ Undo"),
+                    )
+                    .clicked()
+                {
+                    undo(displayer);
+                }
+                if ui
+                    .add_enabled(
+                        !displayer.history.redo.is_empty(),
+                        egui::Button::new("⮫ Redo"),
+                    )
+                    .clicked()
+                {
+                    redo(displayer);
+                }
+            });
+            ui.separator();
+
+            // Number of transactions to undo (negative) or redo (positive) to reach the clicked one
+            let mut jump: isize = 0;
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                if ui
+                    .selectable_label(displayer.history.undo.is_empty(), "Start")
+                    .clicked()
+                {
+                    jump = -(displayer.history.undo.len() as isize);
+                }
+
+                let done = displayer.history.undo.len();
+                for (i, t) in displayer.history.undo.iter().enumerate() {
+                    let text = format!("{} (graph {})", t.label, t.graph);
+                    if ui.selectable_label(i + 1 == done, text).clicked() {
+                        jump = i as isize + 1 - done as isize;
+                    }
+                }
+
+                // Undone transactions, from the next one to redo to the last one
+                for (i, t) in displayer.history.redo.iter().rev().enumerate() {
+                    let text = RichText::new(format!("{} (graph {})", t.label, t.graph)).weak();
+                    if ui.selectable_label(false, text).clicked() {
+                        jump = i as isize + 1;
+                    }
+                }
+            });
+
+            for _ in 0..jump.unsigned_abs() {
+                if jump < 0 {
+                    undo(displayer);
+                } else {
+                    redo(displayer);
+                }
+            }
+        });
+}
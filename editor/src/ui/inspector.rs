@@ -0,0 +1,769 @@
+use std::collections::HashMap;
+
+use eframe::egui::{self, Color32, Context, DragValue, Pos2, Rect, Sense, Ui, pos2, vec2};
+use egui_extras::{Column, TableBuilder};
+use ulid::Ulid;
+
+use graphs::{
+    AttributeValue, Attributes, Eigen, Graph, GraphError, GraphMetrics, MatrixKind, OrientedGraph,
+    Traversal,
+    layout::{ForceLayout, ForceModel, LayerDirection},
+};
+
+use crate::{
+    GraphDisplayer,
+    editor::{
+        Node, NodeShape,
+        actions::{apply_hierarchical_layout, color_graph, spawn_job},
+        jobs::JobOutput,
+        trace::TracePlayer,
+    },
+    ui::{
+        plot::{TRACE_CURRENT, TRACE_FINISHED, TRACE_FRONTIER},
+        transformations::show_transformations,
+    },
+};
+
+pub struct GraphInspector {
+    pub message: String,
+    /// Last computed statistics, along with the index and revision of the graph they were
+    /// computed on
+    pub metrics: Option<((usize, u64), GraphMetrics)>,
+    pub matrix_kind: MatrixKind,
+    /// Last computed spectrum, along with the index and revision of the graph it was computed on
+    pub spectrum: Option<((usize, u64), Eigen)>,
+    /// Key of the attribute about to be added
+    pub new_attribute: String,
+}
+
+pub fn show_graph_inspector(ctx: &Context, displayer: &mut GraphDisplayer) {
+    egui::SidePanel::left("Inspector").show(ctx, |ui| {
+        ui.heading("Graph editor");
+        ui.label(format!(
+            "Graph size : {}",
+            displayer.graphs[displayer.selected_graph].node_count()
+        ));
+
+        // Lists all the current graph's nodes
+        ui.collapsing("Graph nodes", |ui| {
+            let table = TableBuilder::new(ui)
+                .striped(true)
+                .columns(Column::auto(), 3);
+
+            table
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Name");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Position");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Color");
+                    });
+                })
+                .body(|body| {
+                    let size = displayer.graphs[displayer.selected_graph].node_count();
+                    let mut nodes = displayer.graphs[displayer.selected_graph].nodes.iter_mut();
+
+                    body.rows(20.0, size, |mut rows| {
+                        let (id, v) = nodes.next().unwrap();
+                        // If the node is selected in the editor
+                        let selected = displayer.selected_nodes.contains(id);
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.text_edit_singleline(&mut v.name);
+                        });
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(DragValue::new(&mut v.pos.x));
+                                ui.add(DragValue::new(&mut v.pos.y));
+                            });
+                        });
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.color_edit_button_srgba(&mut v.color);
+                        });
+                    });
+                });
+        });
+
+        ui.collapsing("Node style", |ui| show_node_style(ui, displayer));
+
+        // Lists all the current graph's edges
+        ui.collapsing("Graph edges", |ui| {
+            ui.checkbox(&mut displayer.show_weights, "Show weights on edges");
+
+            let table = TableBuilder::new(ui)
+                .id_salt("edges")
+                .striped(true)
+                .columns(Column::auto(), 3);
+
+            table
+                .header(20.0, |mut header| {
+                    header.col(|ui| {
+                        ui.strong("Source");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Target");
+                    });
+                    header.col(|ui| {
+                        ui.strong("Weight");
+                    });
+                })
+                .body(|body| {
+                    let graph = &mut displayer.graphs[displayer.selected_graph];
+                    let size = graph.edge_count();
+                    let mut edges = graph.edges.iter_mut();
+                    let name = |id: &Ulid| graph.nodes.get(id).map_or("?", |n| n.name.as_str());
+
+                    body.rows(20.0, size, |mut rows| {
+                        let (edge, w) = edges.next().unwrap();
+                        // If the edge is selected in the editor
+                        let selected = displayer.selected_edges.contains(edge);
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.label(name(&edge.0));
+                        });
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.label(name(&edge.1));
+                        });
+
+                        rows.set_selected(selected);
+                        rows.col(|ui| {
+                            ui.add(DragValue::new(w).speed(0.1));
+                        });
+                    });
+                });
+        });
+
+        ui.collapsing("Attributes", |ui| show_attributes(ui, displayer));
+
+        ui.collapsing("Statistics", |ui| {
+            if ui.button("Compute").clicked() {
+                spawn_job(displayer, "Statistics", |graph, progress| {
+                    Ok(JobOutput::Metrics(graph.metrics_with_progress(progress)?))
+                });
+            }
+
+            if let Some((revision, metrics)) = &displayer.inspector.metrics
+                && *revision == displayer.graph_revision()
+            {
+                show_metrics(ui, metrics);
+            }
+        });
+
+        ui.collapsing("Layout", |ui| {
+            ui.collapsing("Force-directed", |ui| {
+                show_force_layout(ui, displayer);
+            });
+            ui.collapsing("Hierarchical", |ui| {
+                show_hierarchical_layout(ui, displayer);
+            });
+            ui.collapsing("Presets", |ui| {
+                show_preset_layouts(ui, displayer);
+            });
+        });
+
+        ui.collapsing("Spectrum", |ui| {
+            show_spectrum(ui, displayer);
+        });
+
+        ui.collapsing("Step by step", |ui| {
+            show_trace_player(ui, displayer);
+        });
+
+        ui.collapsing("Transformations", |ui| {
+            show_transformations(ui, displayer);
+        });
+
+        if ui.button("Color graph").clicked() {
+            color_graph(displayer);
+        }
+    });
+}
+
+fn show_metrics(ui: &mut Ui, metrics: &GraphMetrics) {
+    let optional = |v: Option<usize>| v.map_or("∞".to_string(), |v| v.to_string());
+
+    egui::Grid::new("metrics").striped(true).show(ui, |ui| {
+        let mut row = |name: &str, value: String| {
+            ui.label(name);
+            ui.label(value);
+            ui.end_row();
+        };
+
+        row("Nodes", metrics.node_count.to_string());
+        row("Edges", metrics.edge_count.to_string());
+        row("Density", format!("{:.3}", metrics.density));
+        for (name, degree) in [("In", &metrics.in_degree), ("Out", &metrics.out_degree)] {
+            row(
+                &format!("{name} degree (min/max/avg)"),
+                format!("{} / {} / {:.2}", degree.min, degree.max, degree.average),
+            );
+        }
+        row("Diameter", optional(metrics.diameter));
+        row("Radius", optional(metrics.radius));
+        row("Center size", metrics.center.len().to_string());
+        row("Periphery size", metrics.periphery.len().to_string());
+        row("Girth", optional(metrics.girth));
+        row("Directed girth", optional(metrics.directed_girth));
+        row("Triangles", metrics.triangles.to_string());
+        row("Clustering", format!("{:.3}", metrics.clustering));
+    });
+
+    ui.label("Degree distribution");
+    degree_histogram(ui, &metrics.degree_distribution);
+}
+
+/// Draws a bar for each degree, with a height proportional to the number of nodes having it
+fn degree_histogram(ui: &mut Ui, distribution: &[usize]) {
+    let (response, painter) = ui.allocate_painter(vec2(ui.available_width(), 80.0), Sense::hover());
+    let rect = response.rect;
+    let max = distribution.iter().max().copied().unwrap_or(0).max(1) as f32;
+    let bar_width = rect.width() / distribution.len().max(1) as f32;
+
+    painter.rect_filled(rect, 0, ui.visuals().extreme_bg_color);
+
+    for (degree, count) in distribution.iter().enumerate() {
+        let height = rect.height() * *count as f32 / max;
+        let x = rect.min.x + degree as f32 * bar_width;
+        let bar = Rect::from_min_max(
+            pos2(x, rect.max.y - height),
+            pos2(x + bar_width, rect.max.y),
+        );
+
+        painter.rect_filled(
+            bar.shrink2(vec2(bar_width.min(4.0) * 0.1, 0.0)),
+            0,
+            Color32::LIGHT_BLUE,
+        );
+
+        if response
+            .hover_pos()
+            .is_some_and(|p| p.x >= bar.min.x && p.x < bar.max.x)
+        {
+            response
+                .clone()
+                .on_hover_text(format!("Degree {degree} : {count} nodes"));
+        }
+    }
+}
+
+/// Matrices bigger than this aren't displayed
+const MAX_DISPLAYED_MATRIX: usize = 12;
+
+fn show_spectrum(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let revision = displayer.graph_revision();
+    let graph = &displayer.graphs[displayer.selected_graph];
+    let inspector = &mut displayer.inspector;
+    let mut compute = false;
+
+    ui.horizontal(|ui| {
+        let previous = inspector.matrix_kind;
+        egui::ComboBox::from_id_salt("matrix_kind")
+            .selected_text(format!("{:?}", inspector.matrix_kind))
+            .show_ui(ui, |ui| {
+                for kind in [
+                    MatrixKind::Adjacency,
+                    MatrixKind::Laplacian,
+                    MatrixKind::NormalizedLaplacian,
+                ] {
+                    ui.selectable_value(&mut inspector.matrix_kind, kind, format!("{kind:?}"));
+                }
+            });
+        if previous != inspector.matrix_kind {
+            inspector.spectrum = None;
+        }
+
+        compute = ui.button("Compute").clicked();
+    });
+
+    if graph.node_count() <= MAX_DISPLAYED_MATRIX {
+        let matrix = graph.matrix(inspector.matrix_kind);
+        egui::Grid::new("graph_matrix").show(ui, |ui| {
+            for row in matrix.values.iter() {
+                for v in row.iter() {
+                    ui.monospace(format!("{v:.2}"));
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    if let Some((computed, eigen)) = &inspector.spectrum
+        && *computed == revision
+    {
+        ui.label("Eigenvalues");
+        egui::ScrollArea::vertical()
+            .id_salt("eigenvalues")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                for v in eigen.values.iter() {
+                    ui.monospace(format!("{v:.4}"));
+                }
+            });
+
+        if inspector.matrix_kind == MatrixKind::Laplacian {
+            ui.label(format!(
+                "Algebraic connectivity : {:.4}",
+                eigen.values.get(1).copied().unwrap_or(0.0)
+            ));
+        }
+    }
+
+    if compute {
+        let kind = inspector.matrix_kind;
+        spawn_job(displayer, "Spectrum", move |graph, progress| {
+            Ok(JobOutput::Spectrum(
+                kind,
+                graph.spectrum_with_progress(kind, progress)?,
+            ))
+        });
+    }
+
+    if ui.button("Spectral layout").clicked() {
+        spawn_job(displayer, "Spectral layout", |mut graph, progress| {
+            graph.spectral_layout_with_progress(200.0, progress)?;
+            Ok(JobOutput::Positions(positions(&graph)))
+        });
+    }
+}
+
+/// Records a traversal from the selected node, and replays it on the graph
+fn show_trace_player(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    ui.horizontal(|ui| {
+        for traversal in Traversal::ALL {
+            ui.selectable_value(&mut displayer.traversal, traversal, traversal.name());
+        }
+    });
+
+    let start = match Vec::from_iter(displayer.selected_nodes.iter()).as_slice() {
+        [id] => Some(**id),
+        _ => None,
+    };
+    if ui
+        .add_enabled(
+            start.is_some(),
+            egui::Button::new("Start from selected node"),
+        )
+        .on_disabled_hover_text("Select a single node")
+        .clicked()
+        && let Some(start) = start
+        && let Some(events) = displayer
+            .toasts
+            .report(displayer.graphs[displayer.selected_graph].trace(displayer.traversal, &start))
+    {
+        displayer.trace_player = Some(TracePlayer::new(
+            displayer.traversal,
+            displayer.selected_graph,
+            events,
+        ));
+    }
+
+    let time = ui.input(|i| i.time);
+    let mut stop = false;
+    if let Some(player) = &mut displayer.trace_player {
+        ui.separator();
+        ui.label(format!(
+            "{} on graph {} : step {} / {}",
+            player.traversal.name(),
+            player.graph,
+            player.step,
+            player.events.len()
+        ));
+
+        ui.horizontal(|ui| {
+            if ui.button("⏮").on_hover_text("Restart").clicked() {
+                player.step = 0;
+            }
+            if ui.button("⏪").on_hover_text("Step back").clicked() {
+                player.playing = false;
+                player.back();
+            }
+            if player.playing {
+                if ui.button("⏸").on_hover_text("Pause").clicked() {
+                    player.playing = false;
+                }
+            } else if ui.button("▶").on_hover_text("Play").clicked() {
+                player.play(time);
+            }
+            if ui.button("⏩").on_hover_text("Step forward").clicked() {
+                player.playing = false;
+                player.forward();
+            }
+            stop = ui.button("⏹").on_hover_text("Stop").clicked();
+        });
+
+        ui.add(
+            egui::Slider::new(&mut displayer.trace_speed, 0.5..=30.0)
+                .logarithmic(true)
+                .text("Steps per second"),
+        );
+
+        ui.horizontal(|ui| {
+            ui.colored_label(TRACE_CURRENT, "● Current");
+            ui.colored_label(TRACE_FRONTIER, "● Frontier");
+            ui.colored_label(TRACE_FINISHED, "● Visited");
+        });
+    }
+    if stop {
+        displayer.trace_player = None;
+    }
+}
+
+/// Positions of all the nodes of a graph
+fn positions(graph: &OrientedGraph<Node>) -> HashMap<Ulid, Pos2> {
+    graph.nodes.iter().map(|(id, n)| (*id, n.pos)).collect()
+}
+
+/// Number of steps of a one-shot force-directed layout
+const FORCE_LAYOUT_ITERATIONS: usize = 300;
+
+fn show_force_layout(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let layout = &mut displayer.force_layout;
+
+    egui::ComboBox::from_id_salt("force_model")
+        .selected_text(format!("{:?}", layout.model))
+        .show_ui(ui, |ui| {
+            for model in [ForceModel::FruchtermanReingold, ForceModel::ForceAtlas2] {
+                ui.selectable_value(&mut layout.model, model, format!("{model:?}"));
+            }
+        });
+
+    ui.add(egui::Slider::new(&mut layout.repulsion, 0.0..=10.0).text("Repulsion"));
+    ui.add(egui::Slider::new(&mut layout.attraction, 0.0..=10.0).text("Attraction"));
+    ui.add(egui::Slider::new(&mut layout.gravity, 0.0..=1.0).text("Gravity"));
+    ui.add(egui::Slider::new(&mut layout.speed, 0.01..=1.0).text("Speed"));
+
+    if ui.button("Reset settings").clicked() {
+        *layout = ForceLayout {
+            model: layout.model,
+            ..Default::default()
+        };
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Run").clicked() {
+            let layout = displayer.force_layout.clone();
+            let pinned = displayer.selected_nodes.clone();
+            spawn_job(
+                displayer,
+                "Force-directed layout",
+                move |mut graph, progress| {
+                    layout.run_while(&mut graph, &pinned, FORCE_LAYOUT_ITERATIONS, |done| {
+                        progress.set(done as f32 / FORCE_LAYOUT_ITERATIONS as f32);
+                        !progress.is_cancelled()
+                    });
+                    Ok(JobOutput::Positions(positions(&graph)))
+                },
+            );
+        }
+
+        let label = if displayer.simulating {
+            "⏸ Pause"
+        } else {
+            "▶ Simulate"
+        };
+        if ui
+            .button(label)
+            .on_hover_text("Selected nodes are pinned in place")
+            .clicked()
+        {
+            displayer.simulating = !displayer.simulating;
+        }
+    });
+}
+
+/// Edits the appearance of the selected nodes, starting from the style of one of them
+fn show_node_style(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let graph = &mut displayer.graphs[displayer.selected_graph];
+    let Some(mut style) = displayer
+        .selected_nodes
+        .iter()
+        .find_map(|id| graph.nodes.get(id))
+        .cloned()
+    else {
+        ui.label("Select nodes to edit their style");
+        return;
+    };
+
+    let mut changed = false;
+    egui::Grid::new("node_style").num_columns(2).show(ui, |ui| {
+        ui.label("Shape");
+        ui.horizontal(|ui| {
+            for shape in NodeShape::ALL {
+                changed |= ui
+                    .selectable_value(&mut style.shape, shape, shape.name())
+                    .changed();
+            }
+        });
+        ui.end_row();
+
+        ui.label("Size");
+        changed |= ui
+            .add(egui::Slider::new(&mut style.radius, 2.0..=32.0))
+            .changed();
+        ui.end_row();
+
+        ui.label("Color");
+        changed |= ui.color_edit_button_srgba(&mut style.color).changed();
+        ui.end_row();
+
+        ui.label("Border");
+        ui.horizontal(|ui| {
+            changed |= ui
+                .add(
+                    DragValue::new(&mut style.border.width)
+                        .range(0.0..=8.0)
+                        .speed(0.1),
+                )
+                .changed();
+            changed |= ui
+                .color_edit_button_srgba(&mut style.border.color)
+                .changed();
+        });
+        ui.end_row();
+    });
+
+    if changed {
+        displayer.history.label("Edit node style");
+        for id in displayer.selected_nodes.iter() {
+            if let Some(node) = graph.nodes.get_mut(id) {
+                node.shape = style.shape;
+                node.radius = style.radius;
+                node.color = style.color;
+                node.border = style.border;
+            }
+        }
+    }
+}
+
+/// Edits the attributes of the selected node or edge, and picks the attribute used as weight
+fn show_attributes(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let graph = &mut displayer.graphs[displayer.selected_graph];
+
+    let numeric = graph.numeric_edge_attributes();
+    egui::ComboBox::from_label("Path weight")
+        .selected_text(
+            displayer
+                .weight_attribute
+                .as_deref()
+                .unwrap_or("Edge weight"),
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut displayer.weight_attribute, None, "Edge weight");
+            for key in numeric {
+                ui.selectable_value(&mut displayer.weight_attribute, Some(key.clone()), key);
+            }
+        });
+    ui.separator();
+
+    let new_key = &mut displayer.inspector.new_attribute;
+    let changed = if let [id] = Vec::from_iter(displayer.selected_nodes.iter()).as_slice()
+        && let Some(node) = graph.nodes.get_mut(id)
+    {
+        ui.label(format!("Node : {}", node.name));
+        edit_attributes(ui, &mut node.attributes, new_key)
+    } else if displayer.selected_nodes.is_empty()
+        && let [edge] = Vec::from_iter(displayer.selected_edges.iter()).as_slice()
+        && graph.edges.contains_key(edge)
+    {
+        ui.label("Edge");
+        let mut attributes = graph.edge_attributes.remove(edge).unwrap_or_default();
+        let changed = edit_attributes(ui, &mut attributes, new_key);
+        // Only edges with attributes are stored
+        if !attributes.is_empty() {
+            graph.edge_attributes.insert(**edge, attributes);
+        }
+        changed
+    } else {
+        ui.label("Select a single node or edge to edit its attributes");
+        false
+    };
+
+    if changed {
+        displayer.history.label("Edit attributes");
+    }
+}
+
+/// Editor of an attribute map, returns whether it was modified
+fn edit_attributes(ui: &mut Ui, attributes: &mut Attributes, new_key: &mut String) -> bool {
+    let mut changed = false;
+    let mut removed = None;
+
+    egui::Grid::new("attributes")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for (key, value) in attributes.iter_mut() {
+                ui.label(key);
+                changed |= match value {
+                    AttributeValue::Text(text) => ui.text_edit_singleline(text).changed(),
+                    AttributeValue::Number(number) => ui.add(DragValue::new(number)).changed(),
+                    AttributeValue::Bool(boolean) => ui.checkbox(boolean, "").changed(),
+                };
+                if ui.small_button("🗑").clicked() {
+                    removed = Some(key.clone());
+                }
+                ui.end_row();
+            }
+        });
+
+    if let Some(key) = removed {
+        attributes.remove(&key);
+        changed = true;
+    }
+
+    // Adds an attribute of the chosen type, with a default value
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(new_key)
+                .hint_text("New attribute")
+                .desired_width(100.0),
+        );
+        for value in AttributeValue::DEFAULTS {
+            let valid = !new_key.is_empty() && !attributes.contains_key(new_key);
+            if ui
+                .add_enabled(valid, egui::Button::new(value.type_name()))
+                .clicked()
+            {
+                attributes.insert(std::mem::take(new_key), value);
+                changed = true;
+            }
+        }
+    });
+
+    changed
+}
+
+fn show_hierarchical_layout(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let layout = &mut displayer.hierarchical_layout;
+
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut layout.direction, LayerDirection::TopDown, "⬇ Top-down");
+        ui.selectable_value(
+            &mut layout.direction,
+            LayerDirection::LeftRight,
+            "➡ Left-right",
+        );
+    });
+    ui.add(egui::Slider::new(&mut layout.layer_spacing, 16.0..=256.0).text("Layer spacing"));
+    ui.add(egui::Slider::new(&mut layout.node_spacing, 16.0..=256.0).text("Node spacing"));
+    ui.add(egui::Slider::new(&mut layout.sweeps, 0..=32).text("Sweeps"));
+    ui.checkbox(
+        &mut displayer.route_edges,
+        "Route edges through dummy nodes",
+    );
+
+    if ui.button("Apply").clicked() {
+        apply_hierarchical_layout(displayer);
+    }
+}
+
+fn show_preset_layouts(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    ui.checkbox(&mut displayer.layout_selection_only, "Selection only");
+    ui.checkbox(&mut displayer.group_by_color, "Group circle by color");
+
+    let graph = &displayer.graphs[displayer.selected_graph];
+    let root = displayer.layout_root.and_then(|r| graph.nodes.get(&r));
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "Root : {}",
+            root.map_or("highest degree", |r| r.name.as_str())
+        ));
+        let single = displayer.selected_nodes.len() == 1;
+        if ui
+            .add_enabled(single, egui::Button::new("Use selected"))
+            .clicked()
+        {
+            displayer.layout_root = displayer.selected_nodes.iter().next().copied();
+        }
+    });
+
+    let mut layout = None;
+    ui.horizontal_wrapped(|ui| {
+        for preset in PresetLayout::ALL {
+            if ui.button(preset.name()).clicked() {
+                layout = Some(preset);
+            }
+        }
+    });
+
+    if let Some(layout) = layout {
+        // Selected nodes may have been removed since they were selected
+        let nodes: Vec<Ulid> = if displayer.layout_selection_only {
+            displayer
+                .selected_nodes
+                .iter()
+                .filter(|id| graph.nodes.contains_key(id))
+                .copied()
+                .collect()
+        } else {
+            graph.nodes.keys().copied().collect()
+        };
+        let root = displayer.layout_root;
+        let group_by_color = displayer.group_by_color;
+
+        spawn_job(displayer, layout.name(), move |graph, _| {
+            Ok(JobOutput::Positions(match layout {
+                PresetLayout::Circular => graph
+                    .circular_layout(&nodes, |n| group_by_color.then_some(n.color.to_array()))?,
+                PresetLayout::Concentric => graph.concentric_layout(&nodes)?,
+                PresetLayout::RadialTree => {
+                    // The chosen root if it's still laid out, or the node of highest degree
+                    let root = root.filter(|r| nodes.contains(r)).or_else(|| {
+                        let adjacency = graph.undirected_adjacency();
+                        nodes
+                            .iter()
+                            .copied()
+                            .max_by_key(|id| (adjacency.get(id).map_or(0, |a| a.len()), *id))
+                    });
+                    let Some(root) = root else {
+                        return Err(GraphError::NotEnoughNodes {
+                            required: 1,
+                            found: 0,
+                        });
+                    };
+                    graph.radial_tree_layout(&nodes, root)?
+                }
+                PresetLayout::Grid => graph.grid_layout(&nodes)?,
+            }))
+        });
+    }
+}
+
+/// Layouts placing the nodes along a fixed pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PresetLayout {
+    Circular,
+    Concentric,
+    RadialTree,
+    Grid,
+}
+
+impl PresetLayout {
+    const ALL: [Self; 4] = [
+        Self::Circular,
+        Self::Concentric,
+        Self::RadialTree,
+        Self::Grid,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Circular => "Circular",
+            Self::Concentric => "Concentric",
+            Self::RadialTree => "Radial tree",
+            Self::Grid => "Grid",
+        }
+    }
+}
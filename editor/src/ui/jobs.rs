@@ -0,0 +1,38 @@
+use eframe::egui::{self, Align2, Context, ProgressBar, vec2};
+
+use crate::editor::GraphDisplayer;
+
+/// Lists the running background jobs in the top-right corner of the window, with their
+/// progress and a button to cancel them
+pub fn show_jobs(ctx: &Context, displayer: &mut GraphDisplayer) {
+    if displayer.jobs.running.is_empty() {
+        return;
+    }
+
+    let mut cancelled = None;
+    egui::Area::new("jobs".into())
+        .anchor(Align2::RIGHT_TOP, vec2(-12.0, 48.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                for (i, job) in displayer.jobs.running.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} (graph {})", job.name, job.graph));
+                        if ui.small_button("✖").on_hover_text("Cancel").clicked() {
+                            cancelled = Some(i);
+                        }
+                    });
+                    ui.add(
+                        ProgressBar::new(job.progress.get())
+                            .desired_width(200.0)
+                            .animate(true),
+                    );
+                }
+            });
+        });
+
+    if let Some(i) = cancelled {
+        displayer.jobs.cancel(i);
+    }
+    // Check for finished jobs even if nothing happens
+    ctx.request_repaint_after_secs(0.1);
+}
@@ -1,8 +1,10 @@
 use eframe::egui::{self, Context};
 
+use graphs::{Graph, OrientedGraph};
+
 use crate::{
     editor::{GraphDisplayer, GraphTools},
-    graphs::{Graph, OrientedGraph},
+    ui::templates::show_template_menu,
 };
 
 pub fn show_graph_tools(ctx: &Context, tool: &mut GraphTools) {
@@ -26,8 +28,11 @@ pub fn show_graph_selector(ctx: &Context, displayer: &mut GraphDisplayer) {
                 if i == displayer.selected_graph {
                     btn = btn.highlight();
                 }
-                if btn.clicked() {
+                if btn.clicked() && i != displayer.selected_graph {
                     displayer.selected_graph = i;
+                    // The selection belongs to the previous graph
+                    displayer.selected_nodes.clear();
+                    displayer.selected_edges.clear();
                 }
             }
 
@@ -35,6 +40,20 @@ pub fn show_graph_selector(ctx: &Context, displayer: &mut GraphDisplayer) {
             if ui.button("+").clicked() {
                 displayer.graphs.push(OrientedGraph::empty());
             }
+
+            // Add a classic graph to the list
+            ui.menu_button("📐", |ui| show_template_menu(ui, displayer))
+                .response
+                .on_hover_text("New graph from template");
+
+            // Open the random graph generator
+            if ui.button("🎲").on_hover_text("Random graph").clicked() {
+                displayer.generator.open = true;
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.toggle_value(&mut displayer.show_history, "🕘 History");
+            });
         });
     });
 }
@@ -0,0 +1,11 @@
+pub mod context_menu;
+pub mod generator;
+pub mod history;
+pub mod inspector;
+pub mod jobs;
+pub mod misc;
+pub mod plot;
+pub mod templates;
+pub mod toasts;
+pub mod transformations;
+pub mod widgets;
\ No newline at end of file
@@ -0,0 +1,686 @@
+use std::f32::consts::SQRT_2;
+
+use eframe::{
+    egui::{
+        self, Align2, Color32, Context, DragValue, FontId, InputState, Pos2, Rect, Sense, Shape,
+        Stroke, Vec2,
+        epaint::{CircleShape, CubicBezierShape, QuadraticBezierShape},
+        vec2,
+    },
+    emath,
+};
+use ulid::Ulid;
+
+use graphs::{Graph, Real, Spatial, TraceState, Traversal, layout::POINT_RADIUS};
+
+use crate::editor::{GraphDisplayer, GraphTools, Node};
+
+use super::context_menu::ContextMenu;
+
+pub fn plot_graph(ctx: &Context, inputs: &InputState, displayer: &mut GraphDisplayer) {
+    // Advance the layout animation
+    if let Some(transition) = &displayer.transition {
+        if transition.apply(
+            &mut displayer.graphs[transition.graph],
+            ctx.input(|i| i.time),
+        ) {
+            displayer.transition = None;
+        } else {
+            ctx.request_repaint();
+        }
+    }
+
+    // Advance the replay of the traversal
+    if let Some(player) = &mut displayer.trace_player
+        && player.advance(ctx.input(|i| i.time), displayer.trace_speed)
+    {
+        ctx.request_repaint();
+    }
+
+    // Advance the live layout simulation, selected nodes staying in place
+    if displayer.simulating {
+        displayer.force_layout.step(
+            &mut displayer.graphs[displayer.selected_graph],
+            &displayer.selected_nodes,
+        );
+        ctx.request_repaint();
+    }
+
+    egui::CentralPanel::default().show(ctx, |panel| {
+        let reference_rect = panel.response().rect;
+
+        let mut copied_rect = displayer.rect;
+
+        egui::Scene::new().show(panel, &mut copied_rect, |ui| {
+            let bg_response = ui.interact(
+                displayer.rect,
+                ui.id(),
+                if displayer.tool != GraphTools::Look {
+                    Sense::all()
+                } else {
+                    Sense::click()
+                },
+            );
+
+            if let Some(pos) = bg_response.hover_pos() {
+                displayer.last_hovered_position = pos;
+                if inputs.key_pressed(egui::Key::N) {
+                    displayer.history.label("Add node");
+                    displayer.graphs[displayer.selected_graph].insert(Node::at_pos(pos));
+                }
+            }
+
+            let mut pathfind_target: Option<Ulid> = None;
+            // Node under the pointer, and whether a link drag ended this frame
+            let mut pointed_node: Option<Ulid> = None;
+            let mut link_released = false;
+            let mut dragged_node = false;
+            let mut clicked_node = false;
+            let mut node_delta = Vec2::ZERO;
+
+            let nodes: Vec<Shape> = displayer.graphs[displayer.selected_graph]
+                .nodes
+                .iter_mut()
+                .enumerate()
+                .map(|(i, (id, node))| {
+                    let mut color: Color32 = node.color;
+                    let size = Vec2::splat(2.0 * node.radius);
+
+                    if displayer.tool != GraphTools::Look {
+                        let point_rect = Rect::from_center_size(node.pos, size);
+                        let point_id = bg_response.id.with(i);
+                        let point_response = ui.interact(point_rect, point_id, Sense::all());
+
+                        clicked_node = clicked_node || point_response.clicked();
+                        dragged_node = dragged_node || point_response.dragged();
+
+                        match displayer.tool {
+                            GraphTools::Nodes => {
+                                if point_response.drag_started() {
+                                    displayer.history.label("Move nodes");
+                                }
+
+                                // Apply movement to all selected nodes, or only this one if it isn't selected
+                                if point_response.dragged() {
+                                    if displayer.selected_nodes.contains(id) {
+                                        node_delta = point_response.drag_delta();
+                                    } else {
+                                        node.pos += point_response.drag_delta();
+                                    }
+                                }
+
+                                // Registers & applies clicks & drags :
+                                // - Ctrl click/drag : Add nodes to the selection
+                                // - Shift drag : Toggle nodes in selection, instead of selecting them
+                                // - Shift click : select all nodes in the shortest chain from one of the previously
+                                //   selected nodes, to the clicked node
+                                if point_response.clicked() {
+                                    let already_selected = displayer.selected_nodes.contains(id);
+                                    let len = displayer.selected_nodes.len();
+
+                                    if inputs.modifiers.shift {
+                                        pathfind_target = Some(*id);
+                                    }
+                                    // If ctrl
+                                    else if inputs.modifiers.command {
+                                        if already_selected {
+                                            displayer.selected_nodes.remove(id);
+                                        } else {
+                                            displayer.selected_nodes.insert(*id);
+                                        }
+                                    } else {
+                                        displayer.selected_nodes.clear();
+                                        if !already_selected || len > 1 {
+                                            displayer.selected_nodes.insert(*id);
+                                        }
+                                    }
+                                } else if point_response.hovered()
+                                    || displayer.selected_nodes.contains(id)
+                                {
+                                    // Highlight the node
+                                    color = color + ui.style().interact(&point_response).bg_fill;
+                                }
+                            }
+                            GraphTools::Links => {
+                                if point_response.contains_pointer() {
+                                    pointed_node = Some(*id);
+                                }
+
+                                // Dragging from a node draws a link to the node it's released on
+                                if point_response.drag_started() {
+                                    displayer.link_drag = Some((*id, node.pos));
+                                }
+                                if point_response.dragged()
+                                    && let Some(pos) = point_response.interact_pointer_pos()
+                                {
+                                    displayer.link_drag = Some((*id, pos));
+                                }
+                                link_released = link_released || point_response.drag_stopped();
+
+                                if point_response.hovered()
+                                    || displayer.link_drag.is_some_and(|(source, _)| source == *id)
+                                {
+                                    color = color + ui.style().interact(&point_response).bg_fill;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    node_shape(node, color)
+                })
+                .collect();
+
+            // Create the dragged link, the drag being cancelled if the tool changed
+            if (link_released || displayer.tool != GraphTools::Links)
+                && let Some((source, _)) = displayer.link_drag.take()
+                && let Some(target) = pointed_node.filter(|t| link_released && *t != source)
+            {
+                displayer.history.label("Link nodes");
+                let graph = &mut displayer.graphs[displayer.selected_graph];
+                if displayer
+                    .toasts
+                    .report(graph.link(&source, &target, Default::default()))
+                    .is_some()
+                {
+                    displayer.selected_edges = [(source, target)].into();
+                }
+            }
+
+            // Actually do the pathfinding
+            if let Some(target) = pathfind_target {
+                let mut min_cost = Real::INFINITY;
+                let mut min_path = Vec::new();
+                let mut min_node = Ulid(0);
+
+                // Edges are weighted by the chosen attribute, if there is one
+                let graph = &displayer.graphs[displayer.selected_graph];
+                let weighted = displayer
+                    .weight_attribute
+                    .as_ref()
+                    .map(|a| graph.weighted_by(a, Real));
+                let graph = weighted.as_ref().unwrap_or(graph);
+
+                // Find node closest to the target node
+                for n in displayer.selected_nodes.iter() {
+                    if let Some(Some((path, cost))) =
+                        displayer.toasts.report(graph.dijkstra(n, &target))
+                        && cost < min_cost
+                    {
+                        min_cost = cost;
+                        min_path = path;
+                        min_node = *n;
+                    }
+                }
+
+                // If we don't press ctrl, remove all other nodes
+                if !inputs.modifiers.command {
+                    displayer.selected_nodes.retain(|e| *e == min_node);
+                }
+
+                // Add path nodes to the selected collection
+                displayer.selected_nodes.extend(min_path);
+            }
+
+            // Apply drag movement to selected nodes
+            let graph = &mut displayer.graphs[displayer.selected_graph];
+            for id in displayer.selected_nodes.iter() {
+                if let Some(node) = graph.nodes.get_mut(id) {
+                    node.pos += node_delta;
+                }
+            }
+
+            let to_screen = emath::RectTransform::from_to(bg_response.rect, reference_rect);
+
+            // If right-click : open context menu
+            if bg_response.secondary_clicked()
+                && let Some(pos) = bg_response.interact_pointer_pos()
+            {
+                displayer.context_menu = ContextMenu {
+                    just_opened: true,
+                    visible: true,
+                    position: to_screen.transform_pos(pos),
+                };
+            } else
+            // If clicked on an edge while editing, select it
+            if displayer.tool != GraphTools::Look
+                && bg_response.clicked()
+                && !clicked_node
+                && let Some(edge) = bg_response
+                    .interact_pointer_pos()
+                    .and_then(|pos| edge_at(displayer, pos))
+            {
+                // Ctrl click toggles the edge in the selection
+                if !inputs.modifiers.command {
+                    displayer.selected_nodes.clear();
+                    displayer.selected_edges.clear();
+                    displayer.selected_edges.insert(edge);
+                } else if !displayer.selected_edges.remove(&edge) {
+                    displayer.selected_edges.insert(edge);
+                }
+            } else
+            // If clicked on the background, deselect all nodes
+            if bg_response.clicked() && !clicked_node {
+                displayer.selected_nodes.clear();
+                displayer.selected_edges.clear();
+            }
+
+            if displayer.tool != GraphTools::Look {
+                if bg_response.dragged_by(egui::PointerButton::Primary)
+                    && let Some(pos) = bg_response.interact_pointer_pos()
+                {
+                    // If the `Ctrl` key is not down, deselect previous nodes & edges
+                    if !inputs.modifiers.command {
+                        displayer.selected_nodes.clear();
+                        displayer.selected_edges.clear();
+                    }
+
+                    // If dragged started this frame, set origin
+                    if bg_response.drag_started() {
+                        displayer.selection_rect.min = pos;
+                    }
+                    displayer.selection_rect.max = pos;
+                }
+
+                // If drag ended, apply selection
+                if bg_response.drag_stopped() {
+                    // Create the actual rect, with the right min/max points
+                    let actual_rect = Rect::from_two_pos(
+                        displayer.selection_rect.min,
+                        displayer.selection_rect.max,
+                    );
+
+                    // Selects all nodes in the rect, or toggles them if the `Shift` modifier is selected
+                    for (id, node) in displayer.graphs[displayer.selected_graph].nodes() {
+                        if actual_rect.contains(node.pos) {
+                            // If `Shift` is pressed, toggles the selection instead of forcing it
+                            if inputs.modifiers.shift && displayer.selected_nodes.contains(id) {
+                                displayer.selected_nodes.remove(id);
+                            } else {
+                                displayer.selected_nodes.insert(*id);
+                            }
+                        }
+                    }
+
+                    // Same for edges, which are in the rect if both their ends are
+                    let graph = &displayer.graphs[displayer.selected_graph];
+                    for edge in graph.edges.keys() {
+                        let inside = |id: &Ulid| {
+                            graph
+                                .nodes
+                                .get(id)
+                                .is_some_and(|n| actual_rect.contains(n.pos))
+                        };
+                        if inside(&edge.0) && inside(&edge.1) {
+                            if inputs.modifiers.shift && displayer.selected_edges.contains(edge) {
+                                displayer.selected_edges.remove(edge);
+                            } else {
+                                displayer.selected_edges.insert(*edge);
+                            }
+                        }
+                    }
+
+                    // Remove selection rect
+                    displayer.selection_rect = Rect::ZERO;
+                }
+            }
+
+            let graph = &displayer.graphs[displayer.selected_graph];
+            let trace = displayer
+                .trace_player
+                .as_ref()
+                .filter(|p| p.graph == displayer.selected_graph)
+                .map(|p| (p.traversal, p.state()));
+            let mut lines: Vec<Shape> = Vec::new();
+            let mut labels = Vec::new();
+            for (from, to, weight) in graph.edges() {
+                let e = &(*from, *to);
+                let Some(points) = edge_path(displayer, e) else {
+                    continue;
+                };
+                let stroke = if let Some((traversal, trace)) = &trace
+                    && let Some(stroke) = trace_edge_stroke(*traversal, trace, e)
+                {
+                    stroke
+                } else if displayer.selected_edges.contains(e) {
+                    Stroke::new(2.5, Color32::LIGHT_BLUE)
+                } else {
+                    Stroke::new(
+                        1.0,
+                        if displayer.selected_nodes.contains(from)
+                            || displayer.selected_nodes.contains(to)
+                        {
+                            Color32::GREEN
+                        } else {
+                            Color32::GRAY
+                        },
+                    )
+                };
+
+                if displayer.show_weights {
+                    labels.push((path_middle(&points), format!("{weight:.3}")));
+                }
+                lines.extend(arrowhead(&points, stroke));
+                lines.push(Shape::line(points, stroke));
+            }
+
+            let painter = ui.painter();
+            painter.extend(lines);
+            painter.extend(nodes);
+
+            // Rings around the nodes reached by the traversal
+            if let Some((_, trace)) = &trace {
+                for (id, node) in displayer.graphs[displayer.selected_graph].nodes() {
+                    let color = if trace.current == Some(*id) {
+                        TRACE_CURRENT
+                    } else if trace.frontier.contains(id) {
+                        TRACE_FRONTIER
+                    } else if trace.finished.contains(id) {
+                        TRACE_FINISHED
+                    } else {
+                        continue;
+                    };
+                    painter.circle_stroke(
+                        node.pos,
+                        node.radius * SQRT_2 + 3.0,
+                        Stroke::new(2.5, color),
+                    );
+                }
+            }
+
+            // Node names, hidden when zoomed out too far to be readable
+            let zoom = ui
+                .ctx()
+                .layer_transform_to_global(ui.layer_id())
+                .map_or(1.0, |t| t.scaling);
+            if zoom >= LABEL_MIN_ZOOM {
+                for (_, node) in displayer.graphs[displayer.selected_graph].nodes() {
+                    painter.text(
+                        node.pos + vec2(0.0, node.radius * SQRT_2 + 2.0),
+                        Align2::CENTER_TOP,
+                        &node.name,
+                        FontId::proportional(POINT_RADIUS * 1.25),
+                        ui.visuals().text_color(),
+                    );
+                }
+            }
+            for (pos, text) in labels {
+                let galley = painter.layout_no_wrap(
+                    text,
+                    FontId::proportional(POINT_RADIUS * 1.25),
+                    ui.visuals().text_color(),
+                );
+                let rect = Align2::CENTER_CENTER.anchor_size(pos, galley.size());
+                painter.rect_filled(rect.expand(1.5), 2.0, ui.visuals().extreme_bg_color);
+                painter.galley(rect.min, galley, Color32::PLACEHOLDER);
+            }
+            painter.add(Shape::rect_filled(
+                Rect::from_two_pos(displayer.selection_rect.min, displayer.selection_rect.max),
+                0,
+                Color32::from_rgba_premultiplied(255, 200, 0, 35),
+            ));
+
+            // Rubber band of the link being dragged
+            if let Some((source, pos)) = displayer.link_drag
+                && let Some(node) = displayer.graphs[displayer.selected_graph]
+                    .nodes
+                    .get(&source)
+            {
+                painter.add(Shape::dashed_line(
+                    &[node.pos, pos],
+                    Stroke::new(1.5, Color32::LIGHT_BLUE),
+                    6.0,
+                    4.0,
+                ));
+            }
+
+            // Inline weight editor of the selected edge
+            if displayer.tool == GraphTools::Links
+                && let [edge] = Vec::from_iter(displayer.selected_edges.iter().copied()).as_slice()
+            {
+                let edge = *edge;
+
+                if let Some(points) = edge_path(displayer, &edge) {
+                    let middle = path_middle(&points);
+                    let graph = &mut displayer.graphs[displayer.selected_graph];
+                    if let Some(weight) = graph.edges.get_mut(&edge)
+                        && ui
+                            .put(
+                                Rect::from_center_size(middle, vec2(48.0, 18.0)),
+                                DragValue::new(weight).speed(0.1),
+                            )
+                            .changed()
+                    {
+                        displayer.history.label("Edit weight");
+                    }
+                }
+            }
+        });
+
+        displayer.rect = copied_rect;
+    });
+}
+
+/// Zoom level under which node names are hidden
+const LABEL_MIN_ZOOM: f32 = 0.6;
+
+/// Highlights of the traversal replay : the node being processed, the nodes waiting in the
+/// frontier and those done with
+pub const TRACE_CURRENT: Color32 = Color32::YELLOW;
+pub const TRACE_FRONTIER: Color32 = Color32::from_rgb(255, 140, 0);
+pub const TRACE_FINISHED: Color32 = Color32::LIGHT_GREEN;
+
+/// Stroke of an edge examined by the traversal, or part of the tree it built. Edges of
+/// undirected traversals are matched both ways
+fn trace_edge_stroke(traversal: Traversal, trace: &TraceState, e: &(Ulid, Ulid)) -> Option<Stroke> {
+    let undirected = traversal.is_undirected();
+    let matches = |edge: (Ulid, Ulid)| edge == *e || (undirected && edge == (e.1, e.0));
+
+    if trace.edge.is_some_and(matches) {
+        Some(Stroke::new(3.0, TRACE_CURRENT))
+    } else if trace.parents.get(&e.1) == Some(&e.0)
+        || (undirected && trace.parents.get(&e.0) == Some(&e.1))
+    {
+        Some(Stroke::new(2.5, TRACE_FINISHED))
+    } else {
+        None
+    }
+}
+
+/// Maximum distance from an edge at which a click selects it
+const EDGE_PICK_DISTANCE: f32 = POINT_RADIUS / 2.0;
+
+/// Closest edge of the current graph to the given position, if it is close enough
+fn edge_at(displayer: &GraphDisplayer, pos: Pos2) -> Option<(Ulid, Ulid)> {
+    displayer.graphs[displayer.selected_graph]
+        .edges
+        .keys()
+        .filter_map(|e| {
+            let distance = edge_path(displayer, e)?
+                .windows(2)
+                .map(|s| segment_distance(pos, s[0], s[1]))
+                .fold(f32::INFINITY, f32::min);
+            Some((*e, distance))
+        })
+        .filter(|(_, d)| *d <= EDGE_PICK_DISTANCE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(e, _)| e)
+}
+
+/// Offset of the middle of a reciprocal edge from the straight line, relative to its length
+const RECIPROCAL_BEND: f32 = 0.15;
+/// Size of the self-loops, from the center of their node
+const LOOP_SIZE: f32 = POINT_RADIUS * 5.0;
+/// Length of the arrowheads
+const ARROW_LENGTH: f32 = POINT_RADIUS * 1.25;
+
+/// Polyline along which an edge of the current graph is drawn, clipped at the border of its nodes.
+/// Edges follow their route if they have one, reciprocal edges are bent to the side so that both
+/// are visible, and self-loops are drawn above their node.
+fn edge_path(displayer: &GraphDisplayer, e: &(Ulid, Ulid)) -> Option<Vec<Pos2>> {
+    let graph = &displayer.graphs[displayer.selected_graph];
+    let (source, target) = (graph.nodes.get(&e.0)?, graph.nodes.get(&e.1)?);
+    let (from, to) = (source.pos, target.pos);
+
+    let points = if e.0 == e.1 {
+        CubicBezierShape::from_points_stroke(
+            [
+                from,
+                from + vec2(-LOOP_SIZE / 2.0, -LOOP_SIZE),
+                from + vec2(LOOP_SIZE / 2.0, -LOOP_SIZE),
+                from,
+            ],
+            false,
+            Color32::TRANSPARENT,
+            Stroke::NONE,
+        )
+        .flatten(None)
+    } else if let Some(points) = displayer
+        .edge_routes
+        .get(e)
+        .and_then(|r| r.points(from, to))
+    {
+        points
+    } else if graph.edges.contains_key(&(e.1, e.0)) && from != to {
+        // Bend to the right of the direction, the opposite edge bending the other way
+        let normal = (to - from).rot90();
+        QuadraticBezierShape::from_points_stroke(
+            [
+                from,
+                from.lerp(to, 0.5) + normal * 2.0 * RECIPROCAL_BEND,
+                to,
+            ],
+            false,
+            Color32::TRANSPARENT,
+            Stroke::NONE,
+        )
+        .flatten(None)
+    } else {
+        vec![from, to]
+    };
+
+    let mut points = clip_start(points, |p| source.contains(p));
+    points.reverse();
+    let mut points = clip_start(points, |p| target.contains(p));
+    points.reverse();
+
+    (points.len() >= 2).then_some(points)
+}
+
+/// Removes the beginning of the polyline that is inside the shape
+fn clip_start(points: Vec<Pos2>, inside: impl Fn(Pos2) -> bool) -> Vec<Pos2> {
+    let Some(outside) = points.iter().position(|p| !inside(*p)) else {
+        return Vec::new();
+    };
+    if outside == 0 {
+        return points;
+    }
+
+    // Bisection of the crossing segment, to find where it leaves the shape
+    let (a, b) = (points[outside - 1], points[outside]);
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..16 {
+        let t = (low + high) / 2.0;
+        if inside(a.lerp(b, t)) {
+            low = t;
+        } else {
+            high = t;
+        }
+    }
+
+    std::iter::once(a.lerp(b, high))
+        .chain(points[outside..].iter().copied())
+        .collect()
+}
+
+/// Point halfway along the polyline
+fn path_middle(points: &[Pos2]) -> Pos2 {
+    let length: f32 = points.windows(2).map(|s| s[0].distance(s[1])).sum();
+    let mut remaining = length / 2.0;
+
+    for s in points.windows(2) {
+        let segment = s[0].distance(s[1]);
+        if remaining <= segment && segment > 0.0 {
+            return s[0].lerp(s[1], remaining / segment);
+        }
+        remaining -= segment;
+    }
+    points[points.len() - 1]
+}
+
+/// Triangle at the end of the polyline, pointing in the direction of its last segment
+fn arrowhead(points: &[Pos2], stroke: Stroke) -> Option<Shape> {
+    let tip = *points.last()?;
+    let direction = points
+        .iter()
+        .rev()
+        .find(|p| p.distance(tip) > f32::EPSILON)
+        .map(|p| (tip - *p).normalized())?;
+    let length = ARROW_LENGTH + stroke.width;
+    let side = direction.rot90() * length / 3.0;
+    let base = tip - direction * length;
+
+    Some(Shape::convex_polygon(
+        vec![tip, base + side, base - side],
+        stroke.color,
+        Stroke::NONE,
+    ))
+}
+
+/// Shape of a node, filled with the given color
+fn node_shape(node: &Node, fill: Color32) -> Shape {
+    match node.corners() {
+        Some(corners) => Shape::convex_polygon(corners.to_vec(), fill, node.border),
+        None => Shape::Circle(CircleShape {
+            center: node.pos,
+            radius: node.radius,
+            fill,
+            stroke: node.border,
+        }),
+    }
+}
+
+/// Distance from `pos` to the segment `[a, b]`
+fn segment_distance(pos: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_sq() > 0.0 {
+        ((pos - a).dot(ab) / ab.length_sq()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    pos.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use graphs::{TraceState, Traversal};
+    use ulid::Ulid;
+
+    use super::{TRACE_CURRENT, TRACE_FINISHED, trace_edge_stroke};
+
+    #[test]
+    fn undirected_traversals_highlight_both_directions() {
+        let (a, b, c) = (Ulid::new(), Ulid::new(), Ulid::new());
+        let state = TraceState {
+            edge: Some((a, b)),
+            parents: HashMap::from([(c, a)]),
+            ..Default::default()
+        };
+        let color = |traversal, e| trace_edge_stroke(traversal, &state, &e).map(|s| s.color);
+
+        for traversal in Traversal::ALL {
+            assert_eq!(color(traversal, (a, b)), Some(TRACE_CURRENT));
+            assert_eq!(color(traversal, (a, c)), Some(TRACE_FINISHED));
+            assert_eq!(color(traversal, (b, c)), None);
+
+            let (reverse, tree) = (color(traversal, (b, a)), color(traversal, (c, a)));
+            if traversal.is_undirected() {
+                assert_eq!((reverse, tree), (Some(TRACE_CURRENT), Some(TRACE_FINISHED)));
+            } else {
+                assert_eq!((reverse, tree), (None, None));
+            }
+        }
+    }
+}
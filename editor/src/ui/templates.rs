@@ -0,0 +1,124 @@
+use eframe::egui::{DragValue, Ui};
+
+use graphs::{OrientedGraph, Result};
+
+use crate::editor::{GraphDisplayer, Node};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GraphTemplate {
+    Complete,
+    CompleteBipartite,
+    Cycle,
+    Path,
+    Star,
+    Wheel,
+    Grid,
+    Torus,
+    Hypercube,
+    Petersen,
+    KaryTree,
+}
+
+impl GraphTemplate {
+    pub const ALL: [GraphTemplate; 11] = [
+        GraphTemplate::Complete,
+        GraphTemplate::CompleteBipartite,
+        GraphTemplate::Cycle,
+        GraphTemplate::Path,
+        GraphTemplate::Star,
+        GraphTemplate::Wheel,
+        GraphTemplate::Grid,
+        GraphTemplate::Torus,
+        GraphTemplate::Hypercube,
+        GraphTemplate::Petersen,
+        GraphTemplate::KaryTree,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphTemplate::Complete => "Complete K(n)",
+            GraphTemplate::CompleteBipartite => "Complete bipartite K(n, m)",
+            GraphTemplate::Cycle => "Cycle C(n)",
+            GraphTemplate::Path => "Path P(n)",
+            GraphTemplate::Star => "Star S(n)",
+            GraphTemplate::Wheel => "Wheel W(n)",
+            GraphTemplate::Grid => "Grid n × m",
+            GraphTemplate::Torus => "Torus n × m",
+            GraphTemplate::Hypercube => "Hypercube Q(n)",
+            GraphTemplate::Petersen => "Petersen",
+            GraphTemplate::KaryTree => "m-ary tree of depth n",
+        }
+    }
+
+    pub fn build(&self, n: usize, m: usize) -> Result<OrientedGraph<Node>> {
+        Ok(match self {
+            GraphTemplate::Complete => OrientedGraph::complete(n),
+            GraphTemplate::CompleteBipartite => OrientedGraph::complete_bipartite(n, m),
+            GraphTemplate::Cycle => OrientedGraph::cycle(n),
+            GraphTemplate::Path => OrientedGraph::path(n),
+            GraphTemplate::Star => OrientedGraph::star(n),
+            GraphTemplate::Wheel => OrientedGraph::wheel(n),
+            GraphTemplate::Grid => OrientedGraph::grid(n, m),
+            GraphTemplate::Torus => OrientedGraph::torus(n, m),
+            GraphTemplate::Hypercube => OrientedGraph::hypercube(n)?,
+            GraphTemplate::Petersen => OrientedGraph::petersen(),
+            GraphTemplate::KaryTree => OrientedGraph::kary_tree(m, n)?,
+        })
+    }
+}
+
+/// Sizes used to build the templates
+#[derive(Debug)]
+pub struct TemplateParameters {
+    pub n: usize,
+    pub m: usize,
+}
+
+impl Default for TemplateParameters {
+    fn default() -> Self {
+        Self { n: 5, m: 3 }
+    }
+}
+
+/// Most leaves the trees built from the menu may have
+const MAX_TREE_LEAVES: usize = 1024;
+
+/// Contents of the "New graph from template" menu
+pub fn show_template_menu(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let parameters = &mut displayer.template_parameters;
+
+    ui.horizontal(|ui| {
+        ui.label("n");
+        ui.add(DragValue::new(&mut parameters.n).range(0..=64));
+        ui.label("m");
+        ui.add(DragValue::new(&mut parameters.m).range(0..=64));
+    });
+    ui.separator();
+
+    for template in GraphTemplate::ALL {
+        if ui.button(template.name()).clicked() {
+            // Keep the hypercube and the trees to a reasonable size
+            let n = match template {
+                GraphTemplate::Hypercube => parameters.n.min(10),
+                GraphTemplate::KaryTree => (0..=parameters.n)
+                    .take_while(|depth| {
+                        parameters
+                            .m
+                            .checked_pow(*depth as u32)
+                            .is_some_and(|leaves| leaves <= MAX_TREE_LEAVES)
+                    })
+                    .last()
+                    .unwrap_or_default(),
+                _ => parameters.n,
+            };
+
+            if let Some(graph) = displayer.toasts.report(template.build(n, parameters.m)) {
+                displayer.graphs.push(graph);
+                displayer.selected_graph = displayer.graphs.len() - 1;
+                displayer.selected_nodes.clear();
+                displayer.selected_edges.clear();
+            }
+            ui.close_menu();
+        }
+    }
+}
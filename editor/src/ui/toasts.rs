@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use eframe::egui::{self, Align2, Color32, Context, RichText, Sense, vec2};
+
+/// Time during which a toast stays on screen, in seconds
+const TOAST_DURATION: f64 = 4.0;
+
+/// Short-lived error messages, shown in the bottom-right corner of the window
+#[derive(Debug, Default)]
+pub struct Toasts {
+    /// Messages, along with the time they were first shown
+    messages: Vec<(String, Option<f64>)>,
+}
+
+impl Toasts {
+    pub fn error(&mut self, error: impl Display) {
+        self.messages.push((error.to_string(), None));
+    }
+
+    /// Shows an error if the operation failed
+    pub fn report<T, E: Display>(&mut self, result: Result<T, E>) -> Option<T> {
+        result.map_err(|e| self.error(e)).ok()
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        let time = ctx.input(|i| i.time);
+        self.messages
+            .retain(|(_, shown)| shown.is_none_or(|t| time - t < TOAST_DURATION));
+        if self.messages.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new("toasts".into())
+            .anchor(Align2::RIGHT_BOTTOM, vec2(-12.0, -48.0))
+            .show(ctx, |ui| {
+                for (i, (message, shown)) in self.messages.iter_mut().enumerate() {
+                    shown.get_or_insert(time);
+
+                    let response = egui::Frame::popup(ui.style())
+                        .show(ui, |ui| {
+                            ui.label(
+                                RichText::new(format!("⚠ {message}")).color(Color32::LIGHT_RED),
+                            )
+                        })
+                        .response
+                        .interact(Sense::click())
+                        .on_hover_text("Click to dismiss");
+                    if response.clicked() {
+                        dismissed = Some(i);
+                    }
+                }
+            });
+
+        if let Some(i) = dismissed {
+            self.messages.remove(i);
+        }
+        ctx.request_repaint_after_secs(0.25);
+    }
+}
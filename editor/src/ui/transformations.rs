@@ -0,0 +1,137 @@
+use eframe::egui::{self, DragValue, Ui};
+
+use graphs::{OrientedGraph, Product};
+
+use crate::editor::{
+    GraphDisplayer, Node,
+    actions::{contract_selection, transform_graph},
+};
+
+/// Builds a new graph out of the current one, on a worker thread
+type Transformation = Box<dyn FnOnce(&OrientedGraph<Node>) -> OrientedGraph<Node> + Send>;
+
+/// Parameters of the transformations
+#[derive(Debug)]
+pub struct TransformParameters {
+    /// Distance up to which the power graph links nodes
+    pub power: usize,
+    /// Second operand of the binary operations
+    pub other: usize,
+    pub product: Product,
+}
+
+impl Default for TransformParameters {
+    fn default() -> Self {
+        Self {
+            power: 2,
+            other: 0,
+            product: Product::default(),
+        }
+    }
+}
+
+/// Operations building a new graph out of the current one, shown in a new tab
+pub fn show_transformations(ui: &mut Ui, displayer: &mut GraphDisplayer) {
+    let parameters = &mut displayer.transform_parameters;
+    let mut result: Option<(&str, Transformation)> = None;
+
+    ui.horizontal(|ui| {
+        if ui.button("Complement").clicked() {
+            result = Some(("Complement", Box::new(|g| g.complement())));
+        }
+        if ui.button("Transpose").clicked() {
+            result = Some(("Transpose", Box::new(|g| g.transpose())));
+        }
+        if ui.button("Line graph").clicked() {
+            result = Some((
+                "Line graph",
+                Box::new(|g| {
+                    g.line_graph(|a, b| Node {
+                        name: format!("{}→{}", a.name, b.name),
+                        pos: a.pos.lerp(b.pos, 0.5),
+                        ..a.clone()
+                    })
+                }),
+            ));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        if ui.button("Power").clicked() {
+            let k = parameters.power;
+            result = Some(("Power", Box::new(move |g| g.power(k))));
+        }
+        ui.add(DragValue::new(&mut parameters.power).range(1..=16));
+    });
+
+    let contract = ui
+        .add_enabled(
+            displayer.selected_nodes.len() >= 2,
+            egui::Button::new("Contract selection"),
+        )
+        .on_disabled_hover_text("Select at least 2 nodes")
+        .clicked();
+
+    ui.separator();
+    parameters.other = parameters.other.min(displayer.graphs.len() - 1);
+    egui::ComboBox::from_label("With graph")
+        .selected_text(parameters.other.to_string())
+        .show_ui(ui, |ui| {
+            for i in 0..displayer.graphs.len() {
+                ui.selectable_value(&mut parameters.other, i, i.to_string());
+            }
+        });
+
+    // The second operand is copied, as the job runs over a snapshot of the current graph only
+    let other = &displayer.graphs[parameters.other];
+    ui.horizontal(|ui| {
+        if ui.button("Union").clicked() {
+            let other = other.clone();
+            result = Some(("Union", Box::new(move |g| g.union(&other))));
+        }
+        if ui.button("Disjoint union").clicked() {
+            let mut other = other.clone();
+            result = Some((
+                "Disjoint union",
+                Box::new(move |g| {
+                    // Place the other graph on the right of this one
+                    let offset = g.bounding_rect().max.x - other.bounding_rect().min.x;
+                    for node in other.nodes.values_mut() {
+                        node.pos.x += offset;
+                    }
+                    g.disjoint_union(&other)
+                }),
+            ));
+        }
+        if ui.button("Intersection").clicked() {
+            let other = other.clone();
+            result = Some(("Intersection", Box::new(move |g| g.intersection(&other))));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        for product in Product::ALL {
+            ui.selectable_value(&mut parameters.product, product, product.name());
+        }
+        if ui.button("Product").clicked() {
+            let (other, kind) = (other.clone(), parameters.product);
+            result = Some((
+                "Product",
+                Box::new(move |g| {
+                    g.product(&other, kind, |a, b| Node {
+                        name: format!("({}, {})", a.name, b.name),
+                        pos: a.pos + b.pos.to_vec2(),
+                        ..a.clone()
+                    })
+                }),
+            ));
+        }
+    });
+
+    if let Some((name, transformation)) = result {
+        transform_graph(displayer, name, transformation);
+    }
+    if contract {
+        contract_selection(displayer);
+    }
+}
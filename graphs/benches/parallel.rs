@@ -0,0 +1,43 @@
+//! Benchmarks of the algorithms sped up by the `parallel` feature. To compare both modes, run
+//! them once serially and then in parallel against the saved baseline :
+//!
+//! ```sh
+//! cargo bench -p graphs --bench parallel -- --save-baseline serial
+//! cargo bench -p graphs --bench parallel --features parallel -- --baseline serial
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use graphs::{OrientedGraph, emath::Pos2};
+use rand::{SeedableRng, rngs::StdRng};
+
+fn random_graph(n: usize) -> OrientedGraph<Pos2> {
+    OrientedGraph::erdos_renyi_gnp(n, 8.0 / n as f64, &mut StdRng::seed_from_u64(0))
+}
+
+fn centrality(c: &mut Criterion) {
+    let graph = random_graph(400);
+    c.bench_function("betweenness 400", |b| {
+        b.iter(|| black_box(&graph).betweenness_centrality())
+    });
+    c.bench_function("all pairs shortest paths 400", |b| {
+        b.iter(|| black_box(&graph).all_pairs_shortest_paths())
+    });
+}
+
+fn generators(c: &mut Criterion) {
+    c.bench_function("erdos renyi gnp 3000", |b| {
+        b.iter(|| {
+            OrientedGraph::<Pos2>::erdos_renyi_gnp(3000, 0.001, &mut StdRng::seed_from_u64(0))
+        })
+    });
+    c.bench_function("random geometric 3000", |b| {
+        b.iter(|| {
+            OrientedGraph::<Pos2>::random_geometric(3000, 0.02, &mut StdRng::seed_from_u64(0))
+        })
+    });
+}
+
+criterion_group!(benches, centrality, generators);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Value of a custom attribute of a node or an edge
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+/// Custom data attached to a node or an edge, sorted by key
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+impl AttributeValue {
+    /// Default value of each type of attribute
+    pub const DEFAULTS: [AttributeValue; 3] = [
+        AttributeValue::Text(String::new()),
+        AttributeValue::Number(0.0),
+        AttributeValue::Bool(false),
+    ];
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            AttributeValue::Text(_) => "Text",
+            AttributeValue::Number(_) => "Number",
+            AttributeValue::Bool(_) => "Bool",
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            AttributeValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeValue::Text(s) => write!(f, "{s}"),
+            AttributeValue::Number(n) => write!(f, "{n}"),
+            AttributeValue::Bool(b) => write!(f, "{b}"),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use ulid::Ulid;
+
+use crate::{Csr, GraphWeight, OrientedGraph, Progress, Real, Result, parallel, progress::Steps};
+
+/// Weights of the lightest paths between every pair of nodes, rows and columns following the
+/// nodes of `order`. `distances[i][j]` is `None` if `j` can't be reached from `i`
+#[derive(Debug, Clone, Default)]
+pub struct DistanceMatrix<W = Real> {
+    order: Vec<Ulid>,
+    distances: Vec<Vec<Option<W>>>,
+    /// Position of each node in `order`
+    index: HashMap<Ulid, usize>,
+}
+
+impl<W> DistanceMatrix<W> {
+    pub fn new(order: Vec<Ulid>, distances: Vec<Vec<Option<W>>>) -> Self {
+        let index = order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        Self {
+            order,
+            distances,
+            index,
+        }
+    }
+
+    pub fn order(&self) -> &[Ulid] {
+        &self.order
+    }
+
+    pub fn distances(&self) -> &[Vec<Option<W>>] {
+        &self.distances
+    }
+
+    pub fn get(&self, from: &Ulid, to: &Ulid) -> Option<&W> {
+        self.distances[*self.index.get(from)?][*self.index.get(to)?].as_ref()
+    }
+}
+
+impl<W> Csr<W>
+where
+    W: GraphWeight,
+{
+    /// Betweenness centrality of every node, computed with Brandes' algorithm : the number of
+    /// shortest paths (in number of edges) between two other nodes going through it, each
+    /// pair sharing one unit between its shortest paths
+    pub fn betweenness(&self) -> Vec<f64> {
+        // Never cancelled
+        self.betweenness_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    /// Betweenness centrality, the progress being the share of the nodes whose dependencies
+    /// were accumulated
+    pub fn betweenness_with_progress(&self, progress: &impl Progress) -> Result<Vec<f64>> {
+        let n = self.node_count();
+        let steps = Steps::new(progress, n);
+        let centrality = parallel::fold_range(
+            n,
+            || vec![0.0; n],
+            |mut centrality, start| {
+                if steps.is_cancelled() {
+                    return centrality;
+                }
+                for (c, d) in centrality.iter_mut().zip(self.dependencies(start)) {
+                    *c += d;
+                }
+                steps.step();
+                centrality
+            },
+            |mut a, b| {
+                for (a, b) in a.iter_mut().zip(b) {
+                    *a += b;
+                }
+                a
+            },
+        );
+        steps.finish(centrality)
+    }
+
+    /// Share of the shortest paths leaving `start` which go through each node
+    pub fn dependencies(&self, start: usize) -> Vec<f64> {
+        let n = self.node_count();
+        let mut distances: Vec<Option<usize>> = vec![None; n];
+        let mut path_counts = vec![0.0; n];
+        let mut order = Vec::with_capacity(n);
+
+        distances[start] = Some(0);
+        path_counts[start] = 1.0;
+        order.push(start);
+
+        // Breadth first search, `order` working as the queue
+        let mut next_index = 0;
+        while let Some(&node) = order.get(next_index) {
+            next_index += 1;
+            let d = distances[node].unwrap_or_default();
+            for &next in self.successors(node) {
+                if distances[next].is_none() {
+                    distances[next] = Some(d + 1);
+                    order.push(next);
+                }
+                if distances[next] == Some(d + 1) {
+                    path_counts[next] += path_counts[node];
+                }
+            }
+        }
+
+        // Accumulate from the farthest nodes back to the start
+        let mut dependencies = vec![0.0; n];
+        for &node in order.iter().rev() {
+            let d = distances[node].map(|d| d + 1);
+            for &next in self.successors(node) {
+                if distances[next] == d {
+                    dependencies[node] +=
+                        path_counts[node] / path_counts[next] * (1.0 + dependencies[next]);
+                }
+            }
+        }
+        dependencies[start] = 0.0;
+
+        dependencies
+    }
+
+    /// Runs Dijkstra's algorithm from every node
+    pub fn all_pairs_shortest_paths(&self) -> DistanceMatrix<W> {
+        self.all_pairs_shortest_paths_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    /// Runs Dijkstra's algorithm from every node, the progress being the share of them done
+    pub fn all_pairs_shortest_paths_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<DistanceMatrix<W>> {
+        let steps = Steps::new(progress, self.node_count());
+        let distances = parallel::map_range(self.node_count(), |i| {
+            if steps.is_cancelled() {
+                return Vec::new();
+            }
+            let costs = self.dijkstra(i);
+            steps.step();
+            costs
+        });
+        steps.finish(DistanceMatrix::new(self.ids().to_vec(), distances))
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    /// Betweenness centrality of every node, see [`Csr::betweenness`]
+    ///
+    /// ```
+    /// use graphs::{Graph, OrientedGraph, Real};
+    ///
+    /// let mut graph: OrientedGraph<()> = OrientedGraph::empty();
+    /// let [a, b, c] = [(), (), ()].map(|n| graph.insert(n));
+    /// graph.link(&a, &b, Real(1.0))?;
+    /// graph.link(&b, &c, Real(1.0))?;
+    ///
+    /// let centrality = graph.betweenness_centrality();
+    /// assert_eq!((centrality[&a], centrality[&b], centrality[&c]), (0.0, 1.0, 0.0));
+    /// # Ok::<(), graphs::GraphError>(())
+    /// ```
+    pub fn betweenness_centrality(&self) -> HashMap<Ulid, f64> {
+        let csr = Csr::from_graph(self);
+        csr.to_ids(csr.betweenness())
+    }
+
+    pub fn betweenness_centrality_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<HashMap<Ulid, f64>> {
+        let csr = Csr::from_graph(self);
+        Ok(csr.to_ids(csr.betweenness_with_progress(progress)?))
+    }
+
+    /// Weights of the lightest paths between every pair of nodes
+    pub fn all_pairs_shortest_paths(&self) -> DistanceMatrix<W> {
+        Csr::from_graph(self).all_pairs_shortest_paths()
+    }
+
+    pub fn all_pairs_shortest_paths_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<DistanceMatrix<W>> {
+        Csr::from_graph(self).all_pairs_shortest_paths_with_progress(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use emath::Pos2;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use crate::{Csr, Graph, OrientedGraph, Real};
+
+    type G = OrientedGraph<Pos2>;
+
+    fn random_graph(seed: u64) -> G {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut graph = G::erdos_renyi_gnm(30, 90, &mut rng);
+        for weight in graph.edges.values_mut() {
+            *weight = Real(rng.random_range(1..10) as f64);
+        }
+        graph
+    }
+
+    /// Distances from `start` and number of shortest paths to every node
+    fn path_counts(csr: &Csr, start: usize) -> (Vec<Option<usize>>, Vec<f64>) {
+        let distances = csr.bfs_distances(start);
+        let mut counts = vec![0.0; csr.node_count()];
+        counts[start] = 1.0;
+
+        let mut order: Vec<usize> = (0..csr.node_count())
+            .filter(|i| distances[*i].is_some())
+            .collect();
+        order.sort_by_key(|i| distances[*i]);
+        for node in order {
+            for &next in csr.successors(node) {
+                if distances[next] == distances[node].map(|d| d + 1) {
+                    counts[next] += counts[node];
+                }
+            }
+        }
+
+        (distances, counts)
+    }
+
+    #[test]
+    fn brandes_matches_the_definition() {
+        let csr = Csr::from_graph(&random_graph(1));
+        let n = csr.node_count();
+        let paths: Vec<_> = (0..n).map(|s| path_counts(&csr, s)).collect();
+
+        let mut expected = vec![0.0; n];
+        for s in 0..n {
+            for t in (0..n).filter(|t| *t != s) {
+                let Some(d) = paths[s].0[t] else {
+                    continue;
+                };
+                for v in (0..n).filter(|v| *v != s && *v != t) {
+                    if let (Some(a), Some(b)) = (paths[s].0[v], paths[v].0[t])
+                        && a + b == d
+                    {
+                        expected[v] += paths[s].1[v] * paths[v].1[t] / paths[s].1[t];
+                    }
+                }
+            }
+        }
+
+        for (computed, expected) in csr.betweenness().into_iter().zip(expected) {
+            assert!(
+                (computed - expected).abs() < 1e-9,
+                "{computed} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn betweenness_matches_the_serial_sum() {
+        let csr = Csr::from_graph(&random_graph(2));
+        let mut serial = vec![0.0; csr.node_count()];
+        for start in 0..csr.node_count() {
+            for (c, d) in serial.iter_mut().zip(csr.dependencies(start)) {
+                *c += d;
+            }
+        }
+
+        for (computed, serial) in csr.betweenness().into_iter().zip(serial) {
+            assert!((computed - serial).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn betweenness_of_cycles() {
+        // Each node is on the paths between its predecessor and the two nodes after it, and
+        // between the node before its predecessor and its successor
+        let mut graph = G::empty();
+        let ids = [(); 4].map(|_| graph.insert(Pos2::ZERO));
+        for i in 0..4 {
+            graph.link(&ids[i], &ids[(i + 1) % 4], Real(1.0)).unwrap();
+        }
+
+        let centrality = graph.betweenness_centrality();
+        assert!(centrality.values().all(|c| *c == 3.0));
+        // Each pair of opposite nodes is linked by two paths, sharing their unit
+        let undirected = G::cycle(4).betweenness_centrality();
+        assert!(undirected.values().all(|c| *c == 1.0));
+    }
+
+    #[test]
+    fn all_pairs_shortest_paths_match_dijkstra() {
+        let graph = random_graph(3);
+        let matrix = graph.all_pairs_shortest_paths();
+
+        for from in graph.nodes.keys() {
+            for to in graph.nodes.keys() {
+                let expected = graph.dijkstra(from, to).unwrap().map(|(_, cost)| cost);
+                assert_eq!(matrix.get(from, to), expected.as_ref());
+            }
+        }
+    }
+}
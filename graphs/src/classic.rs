@@ -0,0 +1,256 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use emath::{Pos2, pos2, vec2};
+use ulid::Ulid;
+
+use crate::{Graph, GraphError, GraphWeight, OrientedGraph, Result, Spatial, layout::POINT_RADIUS};
+
+/// Distance between two neighboring nodes in the grid-like layouts
+const SPACING: f32 = POINT_RADIUS * 8.0;
+
+/// Most nodes the classic graphs whose size grows exponentially may have
+pub const MAX_NODES: usize = 1 << 16;
+
+/// Classic named graphs, laid out in their usual drawing. As for random graphs, they are
+/// undirected : each edge is stored in both directions with a default weight
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Spatial,
+    W: GraphWeight,
+{
+    /// Complete graph K(n), where all nodes are linked together
+    pub fn complete(n: usize) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        for i in 0..n {
+            for j in i + 1..n {
+                graph.connect(&ids[i], &ids[j], W::default());
+            }
+        }
+        graph
+    }
+
+    /// Complete bipartite graph K(m, n), drawn as two columns
+    pub fn complete_bipartite(m: usize, n: usize) -> Self {
+        let mut graph = Self::empty();
+        let column = |graph: &mut Self, size: usize, x: f32| -> Vec<Ulid> {
+            (0..size)
+                .map(|i| {
+                    let y = (i as f32 - (size as f32 - 1.0) / 2.0) * SPACING;
+                    graph.insert(N::at_pos(pos2(x, y)))
+                })
+                .collect()
+        };
+
+        let left = column(&mut graph, m, -SPACING * 2.0);
+        let right = column(&mut graph, n, SPACING * 2.0);
+        for l in left.iter() {
+            for r in right.iter() {
+                graph.connect(l, r, W::default());
+            }
+        }
+        graph
+    }
+
+    /// Cycle graph C(n)
+    pub fn cycle(n: usize) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        if n > 2 {
+            for i in 0..n {
+                graph.connect(&ids[i], &ids[(i + 1) % n], W::default());
+            }
+        } else if n == 2 {
+            graph.connect(&ids[0], &ids[1], W::default());
+        }
+        graph
+    }
+
+    /// Path graph P(n), drawn as a horizontal line
+    pub fn path(n: usize) -> Self {
+        let mut graph = Self::empty();
+        let ids = Self::insert_line(&mut graph, n, 0.0);
+        for pair in ids.windows(2) {
+            graph.connect(&pair[0], &pair[1], W::default());
+        }
+        graph
+    }
+
+    /// Star graph S(n) : a center linked to `n` leaves
+    pub fn star(n: usize) -> Self {
+        let (mut graph, leaves) = Self::on_circle(n);
+        let center = graph.insert(N::at_pos(Pos2::ZERO));
+        for leaf in leaves.iter() {
+            graph.connect(&center, leaf, W::default());
+        }
+        graph
+    }
+
+    /// Wheel graph W(n) : a cycle of `n` nodes, all linked to a center
+    pub fn wheel(n: usize) -> Self {
+        let mut graph = Self::cycle(n);
+        let rim: Vec<Ulid> = graph.nodes.keys().copied().collect();
+        let center = graph.insert(N::at_pos(Pos2::ZERO));
+        for node in rim.iter() {
+            graph.connect(&center, node, W::default());
+        }
+        graph
+    }
+
+    /// Grid graph of `rows` by `columns` nodes
+    pub fn grid(rows: usize, columns: usize) -> Self {
+        Self::lattice(rows, columns, false)
+    }
+
+    /// Grid graph whose borders wrap around
+    pub fn torus(rows: usize, columns: usize) -> Self {
+        Self::lattice(rows, columns, true)
+    }
+
+    /// Hypercube graph Q(d), whose nodes are the binary words of length `d`, linked if they
+    /// differ by a single bit. Fails if it would have more than [`MAX_NODES`] nodes
+    pub fn hypercube(d: usize) -> Result<Self> {
+        if d > MAX_NODES.ilog2() as usize {
+            return Err(GraphError::TooManyNodes { limit: MAX_NODES });
+        }
+
+        let mut graph = Self::empty();
+        // Each bit moves the node in its own direction, fanned over a half-turn
+        let directions: Vec<_> = (0..d)
+            .map(|bit| {
+                let angle = PI * bit as f32 / d as f32;
+                vec2(angle.cos(), angle.sin()) * SPACING * 1.5
+            })
+            .collect();
+
+        let ids: Vec<Ulid> = (0..1usize << d)
+            .map(|word| {
+                let pos = (0..d)
+                    .filter(|bit| word & (1 << bit) != 0)
+                    .fold(Pos2::ZERO, |p, bit| p + directions[bit]);
+                graph.insert(N::at_pos(pos))
+            })
+            .collect();
+
+        for (word, id) in ids.iter().enumerate() {
+            for bit in 0..d {
+                let other = word ^ (1 << bit);
+                if other > word {
+                    graph.connect(id, &ids[other], W::default());
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Petersen graph, drawn as a pentagon surrounding a pentagram
+    pub fn petersen() -> Self {
+        let mut graph = Self::empty();
+        let radius = SPACING * 2.0;
+        let point = |i: usize, r: f32| {
+            let angle = TAU * i as f32 / 5.0 - FRAC_PI_2;
+            pos2(r * angle.cos(), r * angle.sin())
+        };
+
+        let outer: Vec<Ulid> = (0..5)
+            .map(|i| graph.insert(N::at_pos(point(i, radius))))
+            .collect();
+        let inner: Vec<Ulid> = (0..5)
+            .map(|i| graph.insert(N::at_pos(point(i, radius / 2.0))))
+            .collect();
+
+        for i in 0..5 {
+            graph.connect(&outer[i], &outer[(i + 1) % 5], W::default());
+            graph.connect(&outer[i], &inner[i], W::default());
+            graph.connect(&inner[i], &inner[(i + 2) % 5], W::default());
+        }
+        graph
+    }
+
+    /// Complete `k`-ary tree of the given depth, drawn top-down with the root on top and each
+    /// parent centered above its children. Fails if it would have more than [`MAX_NODES`]
+    /// nodes
+    ///
+    /// ```
+    /// use graphs::{Graph, GraphError, OrientedGraph, classic::MAX_NODES, emath::Pos2};
+    ///
+    /// let tree: OrientedGraph<Pos2> = OrientedGraph::kary_tree(3, 2)?;
+    /// assert_eq!(tree.node_count(), 1 + 3 + 9);
+    ///
+    /// let huge = OrientedGraph::<Pos2>::kary_tree(3, 64);
+    /// assert_eq!(huge.err(), Some(GraphError::TooManyNodes { limit: MAX_NODES }));
+    /// # Ok::<(), GraphError>(())
+    /// ```
+    pub fn kary_tree(k: usize, depth: usize) -> Result<Self> {
+        let depth = if k == 0 { 0 } else { depth };
+        let too_large = GraphError::TooManyNodes { limit: MAX_NODES };
+        // Number of nodes on each level, checked before anything gets allocated
+        let mut size = 0usize;
+        for level in 0..=depth {
+            let width = u32::try_from(level)
+                .ok()
+                .and_then(|level| k.checked_pow(level))
+                .ok_or(too_large.clone())?;
+            size = size.saturating_add(width);
+            if size > MAX_NODES {
+                return Err(too_large);
+            }
+        }
+
+        let mut graph = Self::empty();
+        let leaves = k.pow(depth as u32) as f32;
+        let mut parents: Vec<Ulid> = Vec::new();
+
+        for level in 0..=depth {
+            // Number of leaves under each node of this level
+            let span = k.pow((depth - level) as u32) as f32;
+            let children: Vec<Ulid> = (0..k.pow(level as u32))
+                .map(|i| {
+                    let x = ((i as f32 + 0.5) * span - leaves / 2.0) * SPACING;
+                    graph.insert(N::at_pos(pos2(x, level as f32 * SPACING)))
+                })
+                .collect();
+
+            for (i, child) in children.iter().enumerate().filter(|_| level > 0) {
+                graph.connect(&parents[i / k], child, W::default());
+            }
+            parents = children;
+        }
+
+        Ok(graph)
+    }
+
+    fn lattice(rows: usize, columns: usize, wrap: bool) -> Self {
+        let mut graph = Self::empty();
+        let ids: Vec<Vec<Ulid>> = (0..rows)
+            .map(|r| {
+                Self::insert_line(
+                    &mut graph,
+                    columns,
+                    (r as f32 - (rows as f32 - 1.0) / 2.0) * SPACING,
+                )
+            })
+            .collect();
+
+        for r in 0..rows {
+            for c in 0..columns {
+                if c + 1 < columns || (wrap && columns > 2) {
+                    graph.connect(&ids[r][c], &ids[r][(c + 1) % columns], W::default());
+                }
+                if r + 1 < rows || (wrap && rows > 2) {
+                    graph.connect(&ids[r][c], &ids[(r + 1) % rows][c], W::default());
+                }
+            }
+        }
+        graph
+    }
+
+    /// Inserts `n` nodes on a horizontal line centered on the Y axis, returning their IDs from
+    /// left to right
+    fn insert_line(graph: &mut Self, n: usize, y: f32) -> Vec<Ulid> {
+        (0..n)
+            .map(|i| {
+                let x = (i as f32 - (n as f32 - 1.0) / 2.0) * SPACING;
+                graph.insert(N::at_pos(pos2(x, y)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::{HashMap, HashSet};
+
+use ulid::Ulid;
+
+use crate::{GraphError, GraphWeight, OrientedGraph, Progress, Result};
+
+/// State of a Bron-Kerbosch search over an undirected adjacency map
+struct CliqueSearch<'a> {
+    adjacency: &'a HashMap<Ulid, HashSet<Ulid>>,
+    /// Only keep the largest clique found, and prune the branches that cannot beat it
+    maximum_only: bool,
+    cliques: Vec<Vec<Ulid>>,
+    progress: &'a dyn Progress,
+    cancelled: bool,
+}
+
+impl<'a> CliqueSearch<'a> {
+    fn run(
+        adjacency: &'a HashMap<Ulid, HashSet<Ulid>>,
+        maximum_only: bool,
+        progress: &'a dyn Progress,
+    ) -> Result<Vec<Vec<Ulid>>> {
+        // The empty set would be the only maximal clique of the empty graph
+        if adjacency.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut search = CliqueSearch {
+            adjacency,
+            maximum_only,
+            cliques: Vec::new(),
+            progress,
+            cancelled: false,
+        };
+        search.expand(
+            &mut Vec::new(),
+            adjacency.keys().copied().collect(),
+            HashSet::new(),
+            (0.0, 1.0),
+        );
+
+        if search.cancelled {
+            Err(GraphError::Cancelled)
+        } else {
+            Ok(search.cliques)
+        }
+    }
+
+    /// Recursive step of the algorithm, with `clique` the clique being built (R), `candidates`
+    /// the nodes that can still extend it (P), and `excluded` the nodes already explored (X).
+    /// The branch covers the `(start, length)` part of the whole search, shared between its
+    /// sub-branches to report the progress
+    fn expand(
+        &mut self,
+        clique: &mut Vec<Ulid>,
+        mut candidates: HashSet<Ulid>,
+        mut excluded: HashSet<Ulid>,
+        (start, length): (f32, f32),
+    ) {
+        if candidates.is_empty() {
+            // Nothing can extend the clique anymore : it is maximal
+            if excluded.is_empty() {
+                if !self.maximum_only {
+                    self.cliques.push(clique.clone());
+                } else if self.cliques.first().is_none_or(|c| c.len() < clique.len()) {
+                    self.cliques = vec![clique.clone()];
+                }
+            }
+            return;
+        }
+
+        // This branch can't give a bigger clique than the best one found so far
+        if self.maximum_only
+            && self
+                .cliques
+                .first()
+                .is_some_and(|best| clique.len() + candidates.len() <= best.len())
+        {
+            return;
+        }
+
+        // Choose the pivot with the most neighbors among the candidates, so that the least
+        // branches are explored
+        let pivot = *candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|n| self.adjacency[*n].intersection(&candidates).count())
+            .unwrap();
+
+        let branches: Vec<Ulid> = candidates
+            .difference(&self.adjacency[&pivot])
+            .copied()
+            .collect();
+        let length = length / branches.len() as f32;
+
+        for (i, node) in branches.into_iter().enumerate() {
+            let start = start + i as f32 * length;
+            if self.cancelled || !self.progress.report(start) {
+                self.cancelled = true;
+                return;
+            }
+            let neighbors = &self.adjacency[&node];
+
+            clique.push(node);
+            self.expand(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                (start, length),
+            );
+            clique.pop();
+
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+}
+
+/// These algorithms work on the underlying simple undirected graph : edge directions are
+/// ignored, as well as self-loops
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    /// Lists all the maximal cliques of the graph, using the Bron-Kerbosch algorithm with pivoting
+    pub fn maximal_cliques(&self) -> Vec<HashSet<Ulid>> {
+        // Never cancelled
+        self.maximal_cliques_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    pub fn maximal_cliques_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<Vec<HashSet<Ulid>>> {
+        Ok(
+            CliqueSearch::run(&self.undirected_adjacency(), false, progress)?
+                .into_iter()
+                .map(|c| c.into_iter().collect())
+                .collect(),
+        )
+    }
+
+    /// Finds a clique of maximum size. Runs in exponential time in the worst case
+    ///
+    /// ```
+    /// use graphs::{OrientedGraph, emath::Pos2};
+    ///
+    /// let wheel: OrientedGraph<Pos2> = OrientedGraph::wheel(6);
+    /// assert_eq!(wheel.maximum_clique().len(), 3);
+    /// ```
+    pub fn maximum_clique(&self) -> HashSet<Ulid> {
+        self.maximum_clique_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    pub fn maximum_clique_with_progress(&self, progress: &impl Progress) -> Result<HashSet<Ulid>> {
+        Ok(
+            CliqueSearch::run(&self.undirected_adjacency(), true, progress)?
+                .pop()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    /// Finds an independent set of maximum size, as a maximum clique of the complement graph.
+    /// Runs in exponential time in the worst case
+    pub fn maximum_independent_set(&self) -> HashSet<Ulid> {
+        self.maximum_independent_set_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    pub fn maximum_independent_set_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<HashSet<Ulid>> {
+        let adjacency = self.undirected_adjacency();
+        let complement: HashMap<Ulid, HashSet<Ulid>> = adjacency
+            .iter()
+            .map(|(id, neighbors)| {
+                (
+                    *id,
+                    adjacency
+                        .keys()
+                        .filter(|n| *n != id && !neighbors.contains(*n))
+                        .copied()
+                        .collect(),
+                )
+            })
+            .collect();
+
+        Ok(CliqueSearch::run(&complement, true, progress)?
+            .pop()
+            .unwrap_or_default()
+            .into_iter()
+            .collect())
+    }
+
+    /// Quickly finds a maximal independent set, by repeatedly picking the node of smallest degree
+    /// and removing its neighbors
+    pub fn greedy_independent_set(&self) -> HashSet<Ulid> {
+        let mut adjacency = self.undirected_adjacency();
+        let mut set = HashSet::new();
+
+        while let Some(node) = adjacency
+            .iter()
+            .min_by_key(|(_, neighbors)| neighbors.len())
+            .map(|(id, _)| *id)
+        {
+            let neighbors = adjacency.remove(&node).unwrap();
+            for n in neighbors.iter() {
+                adjacency.remove(n);
+            }
+            for other in adjacency.values_mut() {
+                other.remove(&node);
+                other.retain(|n| !neighbors.contains(n));
+            }
+            set.insert(node);
+        }
+
+        set
+    }
+
+    /// Finds a vertex cover of minimum size, as the complement of a maximum independent set.
+    /// Runs in exponential time in the worst case
+    pub fn minimum_vertex_cover(&self) -> HashSet<Ulid> {
+        self.minimum_vertex_cover_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    pub fn minimum_vertex_cover_with_progress(
+        &self,
+        progress: &impl Progress,
+    ) -> Result<HashSet<Ulid>> {
+        let independent = self.maximum_independent_set_with_progress(progress)?;
+        Ok(self
+            .nodes
+            .keys()
+            .filter(|id| !independent.contains(*id))
+            .copied()
+            .collect())
+    }
+
+    /// Finds a vertex cover at most twice as big as the minimum one, by taking both ends of the
+    /// edges of a maximal matching
+    pub fn approximate_vertex_cover(&self) -> HashSet<Ulid> {
+        let mut cover = HashSet::new();
+
+        for (from, to) in self.edges.keys() {
+            if from != to && !cover.contains(from) && !cover.contains(to) {
+                cover.insert(*from);
+                cover.insert(*to);
+            }
+        }
+
+        cover
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use emath::Pos2;
+    use ulid::Ulid;
+
+    use crate::{Graph, OrientedGraph};
+
+    type G = OrientedGraph<Pos2>;
+
+    /// Whether every edge of the graph has an end in `cover`
+    fn covers(graph: &G, cover: &HashSet<Ulid>) -> bool {
+        graph
+            .edges
+            .keys()
+            .all(|(from, to)| cover.contains(from) || cover.contains(to))
+    }
+
+    /// Whether no edge links two nodes of `set`
+    fn independent(graph: &G, set: &HashSet<Ulid>) -> bool {
+        graph
+            .edges
+            .keys()
+            .all(|(from, to)| !set.contains(from) || !set.contains(to))
+    }
+
+    #[test]
+    fn complete_graphs() {
+        for n in 1..7 {
+            let graph = G::complete(n);
+            let all: HashSet<Ulid> = graph.nodes.keys().copied().collect();
+            assert_eq!(graph.maximal_cliques(), vec![all.clone()]);
+            assert_eq!(graph.maximum_clique(), all);
+            assert_eq!(graph.maximum_independent_set().len(), 1);
+            assert_eq!(graph.minimum_vertex_cover().len(), n - 1);
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let graph = G::empty();
+        assert!(graph.maximal_cliques().is_empty());
+        assert!(graph.maximum_clique().is_empty());
+        assert!(graph.maximum_independent_set().is_empty());
+        assert!(graph.minimum_vertex_cover().is_empty());
+    }
+
+    #[test]
+    fn petersen() {
+        let graph = G::petersen();
+
+        // Triangle-free : the maximal cliques are the 15 edges
+        let cliques = graph.maximal_cliques();
+        assert_eq!(cliques.len(), 15);
+        assert!(cliques.iter().all(|c| c.len() == 2));
+        assert_eq!(graph.maximum_clique().len(), 2);
+
+        let set = graph.maximum_independent_set();
+        assert_eq!(set.len(), 4);
+        assert!(independent(&graph, &set));
+        let greedy = graph.greedy_independent_set();
+        assert!(independent(&graph, &greedy) && greedy.len() <= 4);
+
+        let cover = graph.minimum_vertex_cover();
+        assert_eq!(cover.len(), 6);
+        assert!(covers(&graph, &cover));
+    }
+
+    #[test]
+    fn approximate_vertex_cover() {
+        for graph in [
+            G::petersen(),
+            G::cycle(7),
+            G::path(6),
+            G::complete(5),
+            G::wheel(8),
+        ] {
+            let cover = graph.approximate_vertex_cover();
+            assert!(covers(&graph, &cover));
+            assert!(cover.len() <= 2 * graph.minimum_vertex_cover().len());
+        }
+    }
+}
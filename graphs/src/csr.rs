@@ -0,0 +1,175 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+use ulid::Ulid;
+
+use crate::{Graph, GraphWeight, Real};
+
+/// Frozen compressed-sparse-row view of a graph, for read-only algorithms. Nodes are numbered
+/// by ascending id, and the edges leaving node `i` are stored contiguously, sorted by target
+///
+/// ```
+/// use graphs::{Csr, Graph, OrientedGraph, Real};
+///
+/// let mut graph: OrientedGraph<()> = OrientedGraph::empty();
+/// let a = graph.insert(());
+/// let b = graph.insert(());
+/// graph.link(&a, &b, Real(2.0))?;
+///
+/// let csr = Csr::from_graph(&graph);
+/// let (i, j) = (csr.index(&a).unwrap(), csr.index(&b).unwrap());
+/// assert_eq!(csr.out_edges(i).collect::<Vec<_>>(), [(j, &Real(2.0))]);
+/// assert_eq!(csr.id(j), b);
+/// # Ok::<(), graphs::GraphError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Csr<W = Real> {
+    ids: Vec<Ulid>,
+    /// Edges leaving node `i` are at `offsets[i]..offsets[i + 1]`
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W> Csr<W>
+where
+    W: GraphWeight,
+{
+    /// Snapshots `graph`, ignoring edges to nodes it doesn't contain
+    pub fn from_graph<N>(graph: &impl Graph<N, W>) -> Self {
+        let mut ids: Vec<Ulid> = graph.nodes().map(|(id, _)| *id).collect();
+        ids.sort();
+        let index = |id: &Ulid| ids.binary_search(id).ok();
+
+        let edges: Vec<(usize, usize, W)> = graph
+            .edges()
+            .filter_map(|(from, to, w)| Some((index(from)?, index(to)?, w.clone())))
+            .collect();
+
+        Self::from_edges(ids, edges)
+    }
+
+    /// Same graph with all its edges reversed
+    pub fn transpose(&self) -> Self {
+        let edges = (0..self.node_count())
+            .flat_map(|from| {
+                self.out_edges(from)
+                    .map(move |(to, w)| (to, from, w.clone()))
+            })
+            .collect();
+
+        Self::from_edges(self.ids.clone(), edges)
+    }
+
+    fn from_edges(ids: Vec<Ulid>, mut edges: Vec<(usize, usize, W)>) -> Self {
+        edges.sort_by_key(|(from, to, _)| (*from, *to));
+
+        let mut offsets = vec![0; ids.len() + 1];
+        for (from, _, _) in edges.iter() {
+            offsets[from + 1] += 1;
+        }
+        for i in 0..ids.len() {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let (targets, weights) = edges.into_iter().map(|(_, to, w)| (to, w)).unzip();
+        Csr {
+            ids,
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Ids of the nodes, `ids()[i]` being the id of node `i`
+    pub fn ids(&self) -> &[Ulid] {
+        &self.ids
+    }
+
+    pub fn id(&self, index: usize) -> Ulid {
+        self.ids[index]
+    }
+
+    /// Dense index of a node, `None` if it wasn't in the graph
+    pub fn index(&self, id: &Ulid) -> Option<usize> {
+        self.ids.binary_search(id).ok()
+    }
+
+    /// Indices of the nodes `index` links to
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.targets[self.offsets[index]..self.offsets[index + 1]]
+    }
+
+    /// Edges leaving `index`, as `(to, weight)`
+    pub fn out_edges(&self, index: usize) -> impl Iterator<Item = (usize, &W)> {
+        let range = self.offsets[index]..self.offsets[index + 1];
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter())
+    }
+
+    pub fn out_degree(&self, index: usize) -> usize {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+
+    /// Maps per-node results, following the dense indices, back to the nodes' ids
+    pub fn to_ids<T>(&self, values: impl IntoIterator<Item = T>) -> HashMap<Ulid, T> {
+        self.ids.iter().copied().zip(values).collect()
+    }
+
+    /// Distances, in number of edges, from `start` to every node, `None` for the ones it
+    /// can't reach
+    pub fn bfs_distances(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.node_count()];
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            let d = distances[node].unwrap_or_default();
+            for next in self.successors(node) {
+                if distances[*next].is_none() {
+                    distances[*next] = Some(d + 1);
+                    queue.push_back(*next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Weight of the lightest path from `start` to every node, `None` for the ones it can't
+    /// reach
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<W>> {
+        let mut costs: Vec<Option<W>> = vec![None; self.node_count()];
+        let mut frontier = BinaryHeap::from([Reverse((W::default(), start))]);
+        costs[start] = Some(W::default());
+
+        while let Some(Reverse((cost, node))) = frontier.pop() {
+            // This node was already reached with a smaller cost
+            if costs[node].as_ref().is_some_and(|c| *c < cost) {
+                continue;
+            }
+
+            for (next, w) in self.out_edges(node) {
+                let new_cost = cost.clone() + w.clone();
+                if costs[next].as_ref().is_none_or(|c| new_cost < *c) {
+                    costs[next] = Some(new_cost.clone());
+                    frontier.push(Reverse((new_cost, next)));
+                }
+            }
+        }
+
+        costs
+    }
+}
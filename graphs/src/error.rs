@@ -0,0 +1,55 @@
+use std::{error::Error, fmt::Display};
+
+use ulid::Ulid;
+
+/// Reason why an operation on a graph failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError {
+    /// The node isn't in the graph
+    NodeNotFound(Ulid),
+    /// There is no edge between these nodes, in this direction
+    EdgeNotFound(Ulid, Ulid),
+    /// The given order doesn't contain every node of the graph exactly once
+    InvalidOrder,
+    /// The operation needs more nodes than there are
+    NotEnoughNodes { required: usize, found: usize },
+    /// No graph satisfies the parameters of a generator
+    Unsatisfiable(String),
+    /// The graph to build would have more than `limit` nodes
+    TooManyNodes { limit: usize },
+    /// The operation was cancelled before it was over
+    Cancelled,
+}
+
+/// Result of the operations on graphs
+pub type Result<T, E = GraphError> = std::result::Result<T, E>;
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::NodeNotFound(id) => write!(f, "Node {id} is not in the graph"),
+            GraphError::EdgeNotFound(from, to) => {
+                write!(f, "There is no edge from {from} to {to}")
+            }
+            GraphError::InvalidOrder => {
+                write!(
+                    f,
+                    "The order must contain every node of the graph exactly once"
+                )
+            }
+            GraphError::NotEnoughNodes { required, found } => {
+                write!(
+                    f,
+                    "At least {required} nodes are needed, but there are {found}"
+                )
+            }
+            GraphError::Unsatisfiable(reason) => write!(f, "{reason}"),
+            GraphError::TooManyNodes { limit } => {
+                write!(f, "The graph would have more than {limit} nodes")
+            }
+            GraphError::Cancelled => write!(f, "The operation was cancelled"),
+        }
+    }
+}
+
+impl Error for GraphError {}
@@ -0,0 +1,429 @@
+use std::{collections::HashSet, f32::consts::TAU};
+
+use emath::{Pos2, pos2};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use ulid::Ulid;
+
+use crate::{
+    Graph, GraphError, GraphWeight, OrientedGraph, Progress, Result, Spatial, layout::POINT_RADIUS,
+    parallel,
+};
+
+/// Number of times the random regular graph generator restarts before giving up
+const REGULAR_ATTEMPTS: usize = 100;
+/// Number of times the random regular graph generator draws a pair for the same stubs before
+/// restarting
+const PAIRING_RETRIES: usize = 100;
+
+/// Random graph models. Generated graphs are undirected, each edge being stored in both
+/// directions with a default weight. Given the same seeded random generator, they always have
+/// the same structure (node IDs excepted), whether the `parallel` feature is enabled or not
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Spatial,
+    W: GraphWeight,
+{
+    /// Erdős–Rényi G(n, p) graph : each pair of nodes is linked with probability `p`
+    ///
+    /// ```
+    /// use graphs::{Graph, OrientedGraph, emath::Pos2};
+    /// use rand::{SeedableRng, rngs::StdRng};
+    ///
+    /// let generate = || -> OrientedGraph<Pos2> {
+    ///     OrientedGraph::erdos_renyi_gnp(100, 0.1, &mut StdRng::seed_from_u64(42))
+    /// };
+    /// let (a, b) = (generate(), generate());
+    /// assert_eq!(a.edge_count(), b.edge_count());
+    /// assert_eq!(a.metrics().degree_distribution, b.metrics().degree_distribution);
+    /// ```
+    pub fn erdos_renyi_gnp(n: usize, p: f64, rng: &mut impl Rng) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        let p = p.clamp(0.0, 1.0);
+
+        // Each row draws from its own generator, so that rows can be filled in any order
+        let seeds: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+        let rows = parallel::map_range(n, |i| {
+            let mut rng = StdRng::seed_from_u64(seeds[i]);
+            (i + 1..n)
+                .filter(|_| rng.random_bool(p))
+                .collect::<Vec<_>>()
+        });
+
+        for (i, row) in rows.into_iter().enumerate() {
+            for j in row {
+                graph.connect(&ids[i], &ids[j], W::default());
+            }
+        }
+
+        graph
+    }
+
+    /// Erdős–Rényi G(n, m) graph : `m` edges chosen uniformly among all the possible ones
+    pub fn erdos_renyi_gnm(n: usize, m: usize, rng: &mut impl Rng) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        let m = m.min(n * n.saturating_sub(1) / 2);
+        let mut linked = HashSet::new();
+
+        while linked.len() < m {
+            let (i, j) = (rng.random_range(0..n), rng.random_range(0..n));
+            if i != j && linked.insert((i.min(j), i.max(j))) {
+                graph.connect(&ids[i], &ids[j], W::default());
+            }
+        }
+
+        graph
+    }
+
+    /// Barabási–Albert preferential attachment graph : each new node is linked to `m` existing
+    /// nodes, chosen with a probability proportional to their degree
+    pub fn barabasi_albert(n: usize, m: usize, rng: &mut impl Rng) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        let m = m.clamp(1, n.saturating_sub(1).max(1));
+
+        // Each node appears once per incident edge, so that picking uniformly in this list
+        // follows the degree distribution
+        let mut repeated: Vec<usize> = Vec::new();
+        let mut targets: Vec<usize> = (0..m).collect();
+
+        for source in m..n {
+            for t in targets.iter() {
+                graph.connect(&ids[source], &ids[*t], W::default());
+            }
+            repeated.extend(targets.iter());
+            repeated.extend(std::iter::repeat_n(source, m));
+
+            let mut next = HashSet::new();
+            while next.len() < m {
+                next.insert(repeated[rng.random_range(0..repeated.len())]);
+            }
+            targets = next.into_iter().collect();
+            // Keep the result independent from the hash set ordering
+            targets.sort();
+        }
+
+        graph
+    }
+
+    /// Watts–Strogatz small-world graph : a ring where each node is linked to its `k` nearest
+    /// neighbors, each edge being then rewired to a random node with probability `beta`
+    pub fn watts_strogatz(n: usize, k: usize, beta: f64, rng: &mut impl Rng) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        let half = (k / 2).min(n.saturating_sub(1) / 2);
+        let mut linked: HashSet<(usize, usize)> = HashSet::new();
+        let key = |i: usize, j: usize| (i.min(j), i.max(j));
+
+        for i in 0..n {
+            for j in 1..=half {
+                linked.insert(key(i, (i + j) % n));
+            }
+        }
+
+        for j in 1..=half {
+            for i in 0..n {
+                if !rng.random_bool(beta.clamp(0.0, 1.0)) {
+                    continue;
+                }
+
+                let target = rng.random_range(0..n);
+                let old = key(i, (i + j) % n);
+                // Rewire only if it doesn't create a self-loop or a duplicate edge
+                if target != i && !linked.contains(&key(i, target)) && linked.remove(&old) {
+                    linked.insert(key(i, target));
+                }
+            }
+        }
+
+        let mut linked: Vec<_> = linked.into_iter().collect();
+        linked.sort();
+        for (i, j) in linked {
+            graph.connect(&ids[i], &ids[j], W::default());
+        }
+
+        graph
+    }
+
+    /// Random `d`-regular graph, where all nodes have exactly `d` neighbors. Fails if no such
+    /// graph exists (`n * d` odd or `d >= n`), or if none was found
+    pub fn random_regular(n: usize, d: usize, rng: &mut impl Rng) -> Result<Self> {
+        // Never cancelled
+        Self::random_regular_with_progress(n, d, rng, &|_| true)
+    }
+
+    /// Random regular graph, built with the pairing model : the `d` stubs of every node are
+    /// paired at random, pairs which would create a self-loop or a duplicate edge being drawn
+    /// again a few times before starting over. Dense graphs are built as the complement of a
+    /// sparse one. The progress is the share of the stubs paired by the current attempt
+    pub fn random_regular_with_progress(
+        n: usize,
+        d: usize,
+        rng: &mut impl Rng,
+        progress: &impl Progress,
+    ) -> Result<Self> {
+        if d >= n && n > 0 {
+            return Err(GraphError::Unsatisfiable(
+                "The degree must be lower than the number of nodes".into(),
+            ));
+        }
+        if n % 2 == 1 && d % 2 == 1 {
+            return Err(GraphError::Unsatisfiable(
+                "The number of nodes times the degree must be even".into(),
+            ));
+        }
+
+        // The complement of a (n - 1 - d)-regular graph is d-regular, and has fewer edges to pair
+        let complement = n > 0 && d > (n - 1) / 2;
+        let sparse = if complement { n - 1 - d } else { d };
+
+        for _ in 0..REGULAR_ATTEMPTS {
+            let Some(pairs) = pair_stubs(n, sparse, rng, progress)? else {
+                continue;
+            };
+
+            let (mut graph, ids) = Self::on_circle(n);
+            if complement {
+                for u in 0..n {
+                    for v in u + 1..n {
+                        if !pairs.contains(&(u, v)) {
+                            graph.connect(&ids[u], &ids[v], W::default());
+                        }
+                    }
+                }
+            } else {
+                let mut pairs: Vec<_> = pairs.into_iter().collect();
+                // Keep the result independent from the hash set ordering
+                pairs.sort();
+                for (u, v) in pairs {
+                    graph.connect(&ids[u], &ids[v], W::default());
+                }
+            }
+            return Ok(graph);
+        }
+
+        Err(GraphError::Unsatisfiable(format!(
+            "No {d}-regular graph was found after {REGULAR_ATTEMPTS} attempts"
+        )))
+    }
+
+    /// Uniformly random labeled tree, built from a random Prüfer sequence
+    ///
+    /// ```
+    /// use graphs::{Graph, OrientedGraph, emath::Pos2};
+    /// use rand::{SeedableRng, rngs::StdRng};
+    ///
+    /// let tree: OrientedGraph<Pos2> = OrientedGraph::random_tree(20, &mut StdRng::seed_from_u64(7));
+    /// // 19 edges, stored in both directions
+    /// assert_eq!(tree.edge_count(), 38);
+    /// assert_eq!(tree.metrics().girth, None);
+    /// ```
+    pub fn random_tree(n: usize, rng: &mut impl Rng) -> Self {
+        let (mut graph, ids) = Self::on_circle(n);
+        if n < 2 {
+            return graph;
+        }
+
+        let sequence: Vec<usize> = (0..n - 2).map(|_| rng.random_range(0..n)).collect();
+        let mut degree = vec![1; n];
+        for s in sequence.iter() {
+            degree[*s] += 1;
+        }
+
+        for s in sequence {
+            // Link the smallest leaf to the current element of the sequence
+            let leaf = degree.iter().position(|d| *d == 1).unwrap();
+            graph.connect(&ids[leaf], &ids[s], W::default());
+            degree[leaf] -= 1;
+            degree[s] -= 1;
+        }
+
+        // Only two leaves remain
+        let mut last = (0..n).filter(|i| degree[*i] == 1);
+        if let (Some(u), Some(v)) = (last.next(), last.next()) {
+            graph.connect(&ids[u], &ids[v], W::default());
+        }
+
+        graph
+    }
+
+    /// Random geometric graph : nodes are placed uniformly in a square of side 1, and linked if
+    /// they are closer than `radius`
+    pub fn random_geometric(n: usize, radius: f32, rng: &mut impl Rng) -> Self {
+        let side = layout_radius(n) * 2.0;
+        let points: Vec<Pos2> = (0..n).map(|_| pos2(rng.random(), rng.random())).collect();
+
+        let mut graph = Self::empty();
+        let ids: Vec<Ulid> = points
+            .iter()
+            .map(|p| graph.insert(N::at_pos(((*p - pos2(0.5, 0.5)) * side).to_pos2())))
+            .collect();
+
+        let rows = parallel::map_range(n, |i| {
+            (i + 1..n)
+                .filter(|j| points[i].distance(points[*j]) <= radius)
+                .collect::<Vec<_>>()
+        });
+
+        for (i, row) in rows.into_iter().enumerate() {
+            for j in row {
+                graph.connect(&ids[i], &ids[j], W::default());
+            }
+        }
+
+        graph
+    }
+
+    /// Creates a graph of `n` isolated nodes placed on a circle, returning their IDs in order
+    pub(super) fn on_circle(n: usize) -> (Self, Vec<Ulid>) {
+        let mut graph = Self::empty();
+        let radius = layout_radius(n);
+
+        let ids = (0..n)
+            .map(|i| {
+                let angle = TAU * i as f32 / n as f32;
+                graph.insert(N::at_pos(pos2(radius * angle.cos(), radius * angle.sin())))
+            })
+            .collect();
+
+        (graph, ids)
+    }
+}
+
+/// Pairs the `d` stubs of each of the `n` nodes, as `(u, v)` with `u < v`. Returns `None` if
+/// some stubs couldn't be paired without creating a self-loop or a duplicate edge
+fn pair_stubs(
+    n: usize,
+    d: usize,
+    rng: &mut impl Rng,
+    progress: &impl Progress,
+) -> Result<Option<HashSet<(usize, usize)>>> {
+    let mut stubs: Vec<usize> = (0..n).flat_map(|i| std::iter::repeat_n(i, d)).collect();
+    let total = stubs.len();
+    let mut pairs = HashSet::new();
+
+    while stubs.len() >= 2 {
+        let paired = (0..PAIRING_RETRIES).find_map(|_| {
+            // Two different stubs
+            let a = rng.random_range(0..stubs.len());
+            let b = (a + rng.random_range(1..stubs.len())) % stubs.len();
+            let (u, v) = (stubs[a].min(stubs[b]), stubs[a].max(stubs[b]));
+            (u != v && pairs.insert((u, v))).then_some((a, b))
+        });
+        let Some((a, b)) = paired else {
+            return Ok(None);
+        };
+
+        stubs.swap_remove(a.max(b));
+        stubs.swap_remove(a.min(b));
+        if !progress.report(1.0 - stubs.len() as f32 / total as f32) {
+            return Err(GraphError::Cancelled);
+        }
+    }
+
+    Ok(Some(pairs))
+}
+
+/// Radius of a circle on which `n` nodes can be placed without overlapping
+fn layout_radius(n: usize) -> f32 {
+    (n as f32 * POINT_RADIUS * 4.0 / TAU).max(50.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use emath::Pos2;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::{Direction, Graph, GraphError, OrientedGraph};
+
+    type G = OrientedGraph<Pos2>;
+
+    /// Edges of the graph between the positions of their ends, which unlike the IDs are the
+    /// same from one generation to the next
+    fn structure(graph: &G) -> BTreeSet<[u32; 4]> {
+        graph
+            .edges
+            .keys()
+            .map(|(from, to)| {
+                let (a, b) = (graph.nodes[from], graph.nodes[to]);
+                [a.x, a.y, b.x, b.y].map(f32::to_bits)
+            })
+            .collect()
+    }
+
+    fn is_connected(graph: &G) -> bool {
+        graph.nodes.keys().next().is_none_or(|start| {
+            let search = graph.bfs([*start], Direction::Outgoing, &mut ()).unwrap();
+            search.order.len() == graph.node_count()
+        })
+    }
+
+    #[test]
+    fn same_seed_same_graph() {
+        let generators: [fn(&mut StdRng) -> G; 7] = [
+            |rng| G::erdos_renyi_gnp(30, 0.2, rng),
+            |rng| G::erdos_renyi_gnm(30, 50, rng),
+            |rng| G::barabasi_albert(30, 2, rng),
+            |rng| G::watts_strogatz(30, 4, 0.3, rng),
+            |rng| G::random_regular(30, 5, rng).unwrap(),
+            |rng| G::random_tree(30, rng),
+            |rng| G::random_geometric(30, 0.3, rng),
+        ];
+
+        for generate in generators {
+            let graph = generate(&mut StdRng::seed_from_u64(3));
+            assert_eq!(
+                structure(&graph),
+                structure(&generate(&mut StdRng::seed_from_u64(3)))
+            );
+            assert_ne!(
+                structure(&graph),
+                structure(&generate(&mut StdRng::seed_from_u64(4)))
+            );
+        }
+    }
+
+    #[test]
+    fn gnm_edge_count() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        for m in [0, 1, 17, 45] {
+            assert_eq!(G::erdos_renyi_gnm(10, m, rng).edge_count(), 2 * m);
+        }
+        // More edges than pairs of nodes gives the complete graph
+        assert_eq!(G::erdos_renyi_gnm(10, 100, rng).edge_count(), 90);
+    }
+
+    #[test]
+    fn regular_degrees() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        for (n, d) in [(0, 0), (10, 0), (10, 3), (9, 4), (16, 7), (20, 11), (9, 8)] {
+            let graph = G::random_regular(n, d, rng).unwrap();
+            assert_eq!(graph.node_count(), n);
+            assert!(graph.nodes.keys().all(|id| graph.out_degree(id) == d
+                && graph.in_degree(id) == d
+                && !graph.edges.contains_key(&(*id, *id))));
+        }
+    }
+
+    #[test]
+    fn unsatisfiable_regular_graphs() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        // Checked before the parity, which would overflow
+        for (n, d) in [(5, 5), (4, 6), (3, usize::MAX), (5, 3), (7, 1)] {
+            assert!(matches!(
+                G::random_regular(n, d, rng),
+                Err(GraphError::Unsatisfiable(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn random_trees() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        for n in [0, 1, 2, 3, 10, 50] {
+            let tree = G::random_tree(n, rng);
+            assert_eq!(tree.node_count(), n);
+            assert_eq!(tree.edge_count(), 2 * n.saturating_sub(1));
+            assert!(is_connected(&tree));
+        }
+    }
+}
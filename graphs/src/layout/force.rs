@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use emath::{Vec2, vec2};
+use ulid::Ulid;
+
+use super::IDEAL_LENGTH;
+use crate::{GraphWeight, OrientedGraph, Spatial};
+
+/// Distance under which two nodes are considered on top of each other
+const MIN_DISTANCE: f32 = 0.01;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ForceModel {
+    /// Nodes repel each other in `k² / d`, and edges pull in `d² / k`
+    #[default]
+    FruchtermanReingold,
+    /// Repulsion is scaled by the degrees of the nodes, and edges pull linearly, which spreads
+    /// hubs apart and gives clearer clusters
+    ForceAtlas2,
+}
+
+/// Settings of a force-directed layout. Edges are considered undirected
+#[derive(Debug, Clone)]
+pub struct ForceLayout {
+    pub model: ForceModel,
+    /// Multiplier of the repulsion between all pairs of nodes
+    pub repulsion: f32,
+    /// Multiplier of the attraction along edges
+    pub attraction: f32,
+    /// Strength of the pull towards the origin, keeping disconnected parts together
+    pub gravity: f32,
+    /// Fraction of the force applied at each step
+    pub speed: f32,
+    /// Maximum distance a node can move in one step
+    pub max_step: f32,
+}
+
+impl Default for ForceLayout {
+    fn default() -> Self {
+        Self {
+            model: ForceModel::default(),
+            repulsion: 1.0,
+            attraction: 1.0,
+            gravity: 0.05,
+            speed: 0.1,
+            max_step: IDEAL_LENGTH,
+        }
+    }
+}
+
+impl ForceLayout {
+    /// Moves every node which isn't pinned once, according to the forces applied to it.
+    /// Returns the largest distance a node moved
+    pub fn step<N: Spatial, W: GraphWeight>(
+        &self,
+        graph: &mut OrientedGraph<N, W>,
+        pinned: &HashSet<Ulid>,
+    ) -> f32 {
+        self.step_with_temperature(graph, pinned, self.max_step)
+    }
+
+    /// Runs the layout for a fixed number of steps, while progressively lowering the maximum
+    /// step so that the nodes settle down
+    pub fn run<N: Spatial, W: GraphWeight>(
+        &self,
+        graph: &mut OrientedGraph<N, W>,
+        pinned: &HashSet<Ulid>,
+        iterations: usize,
+    ) {
+        self.run_while(graph, pinned, iterations, |_| true);
+    }
+
+    /// Same as [`ForceLayout::run`], calling `keep_going` with the number of steps done after
+    /// each of them, and stopping early as soon as it returns false
+    pub fn run_while<N: Spatial, W: GraphWeight>(
+        &self,
+        graph: &mut OrientedGraph<N, W>,
+        pinned: &HashSet<Ulid>,
+        iterations: usize,
+        mut keep_going: impl FnMut(usize) -> bool,
+    ) {
+        for i in 0..iterations {
+            let temperature = self.max_step * (1.0 - i as f32 / iterations as f32);
+            self.step_with_temperature(graph, pinned, temperature);
+            if !keep_going(i + 1) {
+                break;
+            }
+        }
+    }
+
+    fn step_with_temperature<N: Spatial, W: GraphWeight>(
+        &self,
+        graph: &mut OrientedGraph<N, W>,
+        pinned: &HashSet<Ulid>,
+        temperature: f32,
+    ) -> f32 {
+        let adjacency = graph.undirected_adjacency();
+        let k = IDEAL_LENGTH;
+
+        let nodes: Vec<(Ulid, Vec2, f32)> = graph
+            .nodes
+            .iter()
+            .map(|(id, n)| (*id, n.pos().to_vec2(), adjacency[id].len() as f32 + 1.0))
+            .collect();
+        let index: HashMap<Ulid, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _, _))| (*id, i))
+            .collect();
+        let mut forces = vec![Vec2::ZERO; nodes.len()];
+
+        // Repulsion between all pairs of nodes
+        for i in 0..nodes.len() {
+            for j in i + 1..nodes.len() {
+                let (delta, d) = direction(nodes[i].1, nodes[j].1, i);
+                let strength = match self.model {
+                    ForceModel::FruchtermanReingold => k * k / d,
+                    ForceModel::ForceAtlas2 => k * nodes[i].2 * nodes[j].2 / d,
+                } * self.repulsion;
+
+                forces[i] += delta * strength;
+                forces[j] -= delta * strength;
+            }
+        }
+
+        // Attraction along edges
+        for (id, neighbors) in adjacency.iter() {
+            let i = index[id];
+            for j in neighbors.iter().map(|n| index[n]).filter(|j| *j > i) {
+                let (delta, d) = direction(nodes[i].1, nodes[j].1, i);
+                let strength = match self.model {
+                    ForceModel::FruchtermanReingold => d * d / k,
+                    ForceModel::ForceAtlas2 => d,
+                } * self.attraction;
+
+                forces[i] -= delta * strength;
+                forces[j] += delta * strength;
+            }
+        }
+
+        // Gravity towards the origin
+        for (force, (_, pos, mass)) in forces.iter_mut().zip(nodes.iter()) {
+            let strength = match self.model {
+                ForceModel::FruchtermanReingold => pos.length(),
+                ForceModel::ForceAtlas2 => k * *mass,
+            } * self.gravity;
+            *force -= pos.normalized() * strength;
+        }
+
+        let mut largest = 0.0_f32;
+        for (force, (id, _, _)) in forces.iter().zip(nodes.iter()) {
+            if pinned.contains(id) {
+                continue;
+            }
+
+            let mut step = *force * self.speed;
+            if step.length() > temperature {
+                step = step.normalized() * temperature;
+            }
+
+            let node = graph.nodes.get_mut(id).unwrap();
+            node.set_pos(node.pos() + step);
+            largest = largest.max(step.length());
+        }
+
+        largest
+    }
+}
+
+/// Unit vector from `b` to `a`, and the distance between them. Nodes on top of each other are
+/// pushed in an arbitrary direction, depending on `seed`
+fn direction(a: Vec2, b: Vec2, seed: usize) -> (Vec2, f32) {
+    let delta = a - b;
+    let d = delta.length();
+
+    if d < MIN_DISTANCE {
+        let angle = seed as f32;
+        (vec2(angle.cos(), angle.sin()), MIN_DISTANCE)
+    } else {
+        (delta / d, d)
+    }
+}
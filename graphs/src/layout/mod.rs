@@ -0,0 +1,11 @@
+pub mod force;
+pub mod presets;
+pub mod sugiyama;
+pub use force::{ForceLayout, ForceModel};
+pub use sugiyama::{HierarchicalLayout, LayerDirection};
+
+/// Radius of the nodes, which layouts and generators keep room for
+pub const POINT_RADIUS: f32 = 8.0;
+
+/// Preferred distance between two linked nodes
+pub const IDEAL_LENGTH: f32 = POINT_RADIUS * 8.0;
@@ -0,0 +1,206 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    f32::consts::TAU,
+};
+
+use emath::{Pos2, Vec2, vec2};
+use ulid::Ulid;
+
+use super::IDEAL_LENGTH;
+use crate::{GraphWeight, OrientedGraph, Result, Spatial};
+
+/// Simple geometric layouts. They only place the given nodes, around the center of their current
+/// positions, and return the new positions instead of applying them so that they can be animated.
+/// They fail if one of the given nodes isn't in the graph
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Spatial,
+    W: GraphWeight,
+{
+    /// Places the nodes on a circle. Nodes of the same group, given by `group`, are placed next
+    /// to each other, with a gap between groups
+    pub fn circular_layout<K: Ord>(
+        &self,
+        nodes: &[Ulid],
+        group: impl Fn(&N) -> K,
+    ) -> Result<HashMap<Ulid, Pos2>> {
+        self.check_nodes(nodes)?;
+        let mut ordered: Vec<Ulid> = nodes.to_vec();
+        ordered.sort();
+        ordered.sort_by_key(|id| group(&self.nodes[id]));
+
+        let mut slots: Vec<Option<Ulid>> = Vec::new();
+        for (i, id) in ordered.iter().enumerate() {
+            if i > 0 && group(&self.nodes[&ordered[i - 1]]) != group(&self.nodes[id]) {
+                slots.push(None);
+            }
+            slots.push(Some(*id));
+        }
+        // Gap between the last group and the first one
+        if slots.contains(&None) {
+            slots.push(None);
+        }
+
+        let center = self.centroid(nodes);
+        let radius = ring_radius(slots.len());
+
+        Ok(slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, id)| id.map(|id| (id, center + on_ring(i, slots.len(), radius))))
+            .collect())
+    }
+
+    /// Places the nodes on concentric circles according to their degree, the biggest hubs
+    /// being at the center
+    pub fn concentric_layout(&self, nodes: &[Ulid]) -> Result<HashMap<Ulid, Pos2>> {
+        self.check_nodes(nodes)?;
+        let adjacency = self.undirected_adjacency();
+        let mut by_degree: HashMap<usize, Vec<Ulid>> = HashMap::new();
+        for id in nodes.iter() {
+            by_degree.entry(adjacency[id].len()).or_default().push(*id);
+        }
+
+        let mut degrees: Vec<usize> = by_degree.keys().copied().collect();
+        degrees.sort_by(|a, b| b.cmp(a));
+
+        let center = self.centroid(nodes);
+        let mut positions = HashMap::new();
+        let mut radius: f32 = 0.0;
+
+        for (ring, degree) in degrees.iter().enumerate() {
+            let mut ids = by_degree.remove(degree).unwrap();
+            ids.sort();
+
+            // A single node in the first ring sits at the center
+            if ring > 0 || ids.len() > 1 {
+                radius = (radius + IDEAL_LENGTH).max(ring_radius(ids.len()));
+            }
+            for (i, id) in ids.iter().enumerate() {
+                positions.insert(*id, center + on_ring(i, ids.len(), radius));
+            }
+        }
+
+        Ok(positions)
+    }
+
+    /// Places the nodes in rings around `root`, according to their distance from it in the
+    /// underlying undirected graph. Each subtree gets an angle proportional to its number of
+    /// leaves. Nodes which cannot be reached from the root are placed on an outer ring
+    pub fn radial_tree_layout(&self, nodes: &[Ulid], root: Ulid) -> Result<HashMap<Ulid, Pos2>> {
+        self.check_nodes(nodes.iter().chain([&root]))?;
+        let allowed: HashSet<Ulid> = nodes.iter().copied().collect();
+        let adjacency = self.undirected_adjacency();
+
+        // BFS tree from the root, only going through the given nodes
+        let mut children: HashMap<Ulid, Vec<Ulid>> = HashMap::new();
+        let mut depth: HashMap<Ulid, usize> = HashMap::from([(root, 0)]);
+        let mut order = vec![root];
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(node) = queue.pop_front() {
+            let mut next: Vec<Ulid> = adjacency[&node]
+                .iter()
+                .filter(|n| allowed.contains(*n) && !depth.contains_key(*n))
+                .copied()
+                .collect();
+            next.sort();
+
+            for n in next {
+                depth.insert(n, depth[&node] + 1);
+                children.entry(node).or_default().push(n);
+                order.push(n);
+                queue.push_back(n);
+            }
+        }
+
+        // Number of leaves under each node, computed from the deepest nodes up
+        let mut leaves: HashMap<Ulid, usize> = HashMap::new();
+        for node in order.iter().rev() {
+            let count = children
+                .get(node)
+                .map_or(1, |c| c.iter().map(|c| leaves[c]).sum());
+            leaves.insert(*node, count);
+        }
+
+        let center = self.centroid(nodes);
+        let mut positions = HashMap::from([(root, center)]);
+        // Angular wedge of each node, as (start, size)
+        let mut wedges: HashMap<Ulid, (f32, f32)> = HashMap::from([(root, (0.0, TAU))]);
+
+        for node in order.iter() {
+            let (mut start, size) = wedges[node];
+            for child in children.get(node).into_iter().flatten() {
+                let share = size * leaves[child] as f32 / leaves[node] as f32;
+                let angle = start + share / 2.0;
+                let radius = depth[child] as f32 * IDEAL_LENGTH * 1.5;
+
+                positions.insert(*child, center + vec2(angle.cos(), angle.sin()) * radius);
+                wedges.insert(*child, (start, share));
+                start += share;
+            }
+        }
+
+        // Unreachable nodes
+        let max_depth = depth.values().max().copied().unwrap_or(0);
+        let mut remaining: Vec<Ulid> = nodes
+            .iter()
+            .filter(|id| !depth.contains_key(*id))
+            .copied()
+            .collect();
+        remaining.sort();
+        let radius = ring_radius(remaining.len()).max((max_depth + 1) as f32 * IDEAL_LENGTH * 1.5);
+        for (i, id) in remaining.iter().enumerate() {
+            positions.insert(*id, center + on_ring(i, remaining.len(), radius));
+        }
+
+        Ok(positions)
+    }
+
+    /// Places the nodes on a square grid, keeping their current reading order (top to bottom,
+    /// then left to right)
+    pub fn grid_layout(&self, nodes: &[Ulid]) -> Result<HashMap<Ulid, Pos2>> {
+        self.check_nodes(nodes)?;
+        let mut ordered: Vec<Ulid> = nodes.to_vec();
+        ordered.sort_by(|a, b| {
+            let (a, b) = (self.nodes[a].pos(), self.nodes[b].pos());
+            a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+        });
+
+        let columns = (nodes.len() as f32).sqrt().ceil().max(1.0) as usize;
+        let rows = nodes.len().div_ceil(columns);
+        let origin = self.centroid(nodes)
+            - vec2(columns as f32 - 1.0, rows as f32 - 1.0) * IDEAL_LENGTH / 2.0;
+
+        Ok(ordered
+            .into_iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let cell = vec2((i % columns) as f32, (i / columns) as f32);
+                (id, origin + cell * IDEAL_LENGTH)
+            })
+            .collect())
+    }
+
+    /// Average position of the given nodes
+    fn centroid(&self, nodes: &[Ulid]) -> Pos2 {
+        if nodes.is_empty() {
+            return Pos2::ZERO;
+        }
+        let sum = nodes
+            .iter()
+            .fold(Vec2::ZERO, |sum, id| sum + self.nodes[id].pos().to_vec2());
+        (sum / nodes.len() as f32).to_pos2()
+    }
+}
+
+/// Radius of a circle on which `n` nodes can be placed without overlapping
+fn ring_radius(n: usize) -> f32 {
+    (n as f32 * IDEAL_LENGTH / TAU).max(IDEAL_LENGTH)
+}
+
+/// Offset of the `i`-th of `n` points regularly placed on a circle, starting from the top
+fn on_ring(i: usize, n: usize, radius: f32) -> Vec2 {
+    let angle = TAU * i as f32 / n.max(1) as f32 - TAU / 4.0;
+    vec2(angle.cos(), angle.sin()) * radius
+}
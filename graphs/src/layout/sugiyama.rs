@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use emath::{Pos2, pos2};
+use ulid::Ulid;
+
+use super::IDEAL_LENGTH;
+use crate::{Graph, GraphWeight, OrientedGraph, Spatial};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LayerDirection {
+    #[default]
+    TopDown,
+    LeftRight,
+}
+
+/// Settings of the layered (Sugiyama) layout, which draws edges going in the same direction as
+/// much as possible, with few crossings
+#[derive(Debug, Clone)]
+pub struct HierarchicalLayout {
+    pub direction: LayerDirection,
+    /// Distance between two consecutive layers
+    pub layer_spacing: f32,
+    /// Minimal distance between two nodes of the same layer
+    pub node_spacing: f32,
+    /// Number of up and down passes of the crossing minimisation
+    pub sweeps: usize,
+}
+
+impl Default for HierarchicalLayout {
+    fn default() -> Self {
+        Self {
+            direction: LayerDirection::default(),
+            layer_spacing: IDEAL_LENGTH * 1.5,
+            node_spacing: IDEAL_LENGTH,
+            sweeps: 8,
+        }
+    }
+}
+
+/// Node of the layered graph : either a node of the graph, or a dummy node splitting an edge
+/// spanning several layers
+#[derive(Debug, Clone, Copy)]
+struct LayerNode {
+    node: Option<Ulid>,
+    layer: usize,
+}
+
+impl HierarchicalLayout {
+    /// Places the nodes of the graph in layers. Returns the bend points of the edges spanning
+    /// several layers, which go through the dummy nodes of the layout, from source to target
+    pub fn apply<N: Spatial, W: GraphWeight>(
+        &self,
+        graph: &mut OrientedGraph<N, W>,
+    ) -> HashMap<(Ulid, Ulid), Vec<Pos2>> {
+        let mut ids: Vec<Ulid> = graph.nodes.keys().copied().collect();
+        ids.sort();
+
+        let edges = acyclic_edges(graph, &ids);
+        let layers = assign_layers(&ids, &edges);
+
+        // Build the layered graph, splitting long edges with dummy nodes
+        let mut vertices: Vec<LayerNode> = ids
+            .iter()
+            .map(|id| LayerNode {
+                node: Some(*id),
+                layer: layers[id],
+            })
+            .collect();
+        let index: HashMap<Ulid, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut links: Vec<(usize, usize)> = Vec::new();
+        let mut chains: Vec<((Ulid, Ulid), Vec<usize>)> = Vec::new();
+
+        for edge in edges.iter() {
+            let (from, to) = (index[&edge.0], index[&edge.1]);
+            let mut previous = from;
+            let mut chain = Vec::new();
+
+            for layer in vertices[from].layer + 1..vertices[to].layer {
+                vertices.push(LayerNode { node: None, layer });
+                links.push((previous, vertices.len() - 1));
+                previous = vertices.len() - 1;
+                chain.push(previous);
+            }
+            links.push((previous, to));
+
+            if !chain.is_empty() {
+                chains.push((*edge, chain));
+            }
+        }
+
+        let order = self.minimise_crossings(&vertices, &links);
+        let coordinates = self.assign_coordinates(&order, &links, vertices.len());
+
+        let position = |v: usize| -> Pos2 {
+            let depth = vertices[v].layer as f32 * self.layer_spacing;
+            match self.direction {
+                LayerDirection::TopDown => pos2(coordinates[v], depth),
+                LayerDirection::LeftRight => pos2(depth, coordinates[v]),
+            }
+        };
+
+        for (v, vertex) in vertices.iter().enumerate() {
+            if let Some(id) = vertex.node {
+                graph.nodes.get_mut(&id).unwrap().set_pos(position(v));
+            }
+        }
+
+        let mut routes = HashMap::new();
+        for (edge, chain) in chains {
+            // Dummy nodes go from the source to the target of the acyclic edge, whatever the
+            // direction of the original one
+            let forward: Vec<Pos2> = chain.into_iter().map(position).collect();
+            let backward: Vec<Pos2> = forward.iter().rev().copied().collect();
+
+            if graph.linked(&edge.0, &edge.1) {
+                routes.insert(edge, forward);
+            }
+            if graph.linked(&edge.1, &edge.0) {
+                routes.insert((edge.1, edge.0), backward);
+            }
+        }
+
+        routes
+    }
+
+    /// Orders the nodes of each layer with the barycenter heuristic, keeping the order with the
+    /// least crossings
+    fn minimise_crossings(
+        &self,
+        vertices: &[LayerNode],
+        links: &[(usize, usize)],
+    ) -> Vec<Vec<usize>> {
+        let layer_count = vertices.iter().map(|v| v.layer + 1).max().unwrap_or(0);
+        let mut order: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+        for (i, v) in vertices.iter().enumerate() {
+            order[v.layer].push(i);
+        }
+
+        let mut above: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        let mut below: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        for (from, to) in links.iter() {
+            below[*from].push(*to);
+            above[*to].push(*from);
+        }
+
+        let mut best = order.clone();
+        let mut best_crossings = count_crossings(&order, &below);
+
+        for sweep in 0..self.sweeps {
+            let downwards = sweep % 2 == 0;
+            let layers: Vec<usize> = if downwards {
+                (1..layer_count).collect()
+            } else {
+                (0..layer_count.saturating_sub(1)).rev().collect()
+            };
+
+            for layer in layers {
+                let (fixed, neighbors) = if downwards {
+                    (layer - 1, &above)
+                } else {
+                    (layer + 1, &below)
+                };
+                let rank: HashMap<usize, usize> = order[fixed]
+                    .iter()
+                    .enumerate()
+                    .map(|(r, v)| (*v, r))
+                    .collect();
+
+                // Nodes without neighbors keep their current rank
+                let barycenters: HashMap<usize, f32> = order[layer]
+                    .iter()
+                    .enumerate()
+                    .map(|(r, v)| {
+                        let ranks = &neighbors[*v];
+                        let barycenter = if ranks.is_empty() {
+                            r as f32
+                        } else {
+                            ranks.iter().map(|n| rank[n] as f32).sum::<f32>() / ranks.len() as f32
+                        };
+                        (*v, barycenter)
+                    })
+                    .collect();
+
+                order[layer].sort_by(|a, b| barycenters[a].total_cmp(&barycenters[b]));
+            }
+
+            let crossings = count_crossings(&order, &below);
+            if crossings < best_crossings {
+                best_crossings = crossings;
+                best = order.clone();
+            }
+        }
+
+        best
+    }
+
+    /// Places each node as close as possible to the average position of its neighbors, while
+    /// keeping the order of the layers and the spacing between nodes
+    fn assign_coordinates(
+        &self,
+        order: &[Vec<usize>],
+        links: &[(usize, usize)],
+        count: usize,
+    ) -> Vec<f32> {
+        let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for (from, to) in links.iter() {
+            neighbors[*from].push(*to);
+            neighbors[*to].push(*from);
+        }
+
+        let mut x = vec![0.0; count];
+        for layer in order.iter() {
+            for (r, v) in layer.iter().enumerate() {
+                x[*v] = (r as f32 - (layer.len() as f32 - 1.0) / 2.0) * self.node_spacing;
+            }
+        }
+
+        for _ in 0..self.sweeps {
+            for layer in order.iter() {
+                let desired: Vec<f32> = layer
+                    .iter()
+                    .map(|v| {
+                        if neighbors[*v].is_empty() {
+                            x[*v]
+                        } else {
+                            neighbors[*v].iter().map(|n| x[*n]).sum::<f32>()
+                                / neighbors[*v].len() as f32
+                        }
+                    })
+                    .collect();
+
+                for (v, pos) in layer.iter().zip(spaced_fit(&desired, self.node_spacing)) {
+                    x[*v] = pos;
+                }
+            }
+        }
+
+        x
+    }
+}
+
+/// Lists the edges of the graph without self-loops, reversing the edges which close a cycle so
+/// that the result is acyclic. Edges going both ways are merged
+fn acyclic_edges<N, W: GraphWeight>(
+    graph: &OrientedGraph<N, W>,
+    ids: &[Ulid],
+) -> Vec<(Ulid, Ulid)> {
+    let mut successors: HashMap<Ulid, Vec<Ulid>> = HashMap::new();
+    for (from, to) in graph.edges.keys() {
+        if from != to && graph.nodes.contains_key(from) && graph.nodes.contains_key(to) {
+            successors.entry(*from).or_default().push(*to);
+        }
+    }
+    for s in successors.values_mut() {
+        s.sort();
+    }
+
+    // Iterative DFS, edges towards nodes on the current path being back edges
+    let mut visited = HashSet::new();
+    let mut on_path = HashSet::new();
+    let mut edges = Vec::new();
+    let mut pairs = HashSet::new();
+
+    for root in ids.iter() {
+        if !visited.insert(*root) {
+            continue;
+        }
+        on_path.insert(*root);
+        let mut stack = vec![(*root, 0)];
+
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            match successors.get(&node).and_then(|s| s.get(*next)) {
+                Some(target) => {
+                    *next += 1;
+                    let target = *target;
+                    let edge = if on_path.contains(&target) {
+                        (target, node)
+                    } else {
+                        (node, target)
+                    };
+
+                    if pairs.insert(edge) {
+                        edges.push(edge);
+                    }
+
+                    if visited.insert(target) {
+                        on_path.insert(target);
+                        stack.push((target, 0));
+                    }
+                }
+                None => {
+                    on_path.remove(&node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// Longest path layering : each node is one layer below its lowest predecessor
+fn assign_layers(ids: &[Ulid], edges: &[(Ulid, Ulid)]) -> HashMap<Ulid, usize> {
+    let mut successors: HashMap<Ulid, Vec<Ulid>> = HashMap::new();
+    let mut in_degree: HashMap<Ulid, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    for (from, to) in edges.iter() {
+        successors.entry(*from).or_default().push(*to);
+        *in_degree.get_mut(to).unwrap() += 1;
+    }
+
+    let mut layers: HashMap<Ulid, usize> = ids.iter().map(|id| (*id, 0)).collect();
+    let mut queue: VecDeque<Ulid> = ids
+        .iter()
+        .filter(|id| in_degree[*id] == 0)
+        .copied()
+        .collect();
+
+    while let Some(node) = queue.pop_front() {
+        for next in successors.get(&node).into_iter().flatten() {
+            let layer = layers[&node] + 1;
+            let current = layers.get_mut(next).unwrap();
+            *current = (*current).max(layer);
+
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    layers
+}
+
+/// Number of edge crossings between consecutive layers
+fn count_crossings(order: &[Vec<usize>], below: &[Vec<usize>]) -> usize {
+    let mut crossings = 0;
+
+    for pair in order.windows(2) {
+        let rank: HashMap<usize, usize> =
+            pair[1].iter().enumerate().map(|(r, v)| (*v, r)).collect();
+        let segments: Vec<(usize, usize)> = pair[0]
+            .iter()
+            .enumerate()
+            .flat_map(|(r, v)| below[*v].iter().map(move |n| (r, *n)))
+            .map(|(r, n)| (r, rank[&n]))
+            .collect();
+
+        for (i, a) in segments.iter().enumerate() {
+            for b in segments[i + 1..].iter() {
+                if (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1) {
+                    crossings += 1;
+                }
+            }
+        }
+    }
+
+    crossings
+}
+
+/// Closest positions to `desired` (in the least squares sense) which stay in the same order,
+/// and are at least `spacing` apart, computed with the pool adjacent violators algorithm
+fn spaced_fit(desired: &[f32], spacing: f32) -> Vec<f32> {
+    // Removing the spacing turns the problem into a monotonic regression
+    let targets: Vec<f32> = desired
+        .iter()
+        .enumerate()
+        .map(|(i, d)| d - i as f32 * spacing)
+        .collect();
+
+    // Blocks of consecutive values merged together, as (mean, size)
+    let mut blocks: Vec<(f32, usize)> = Vec::new();
+    for t in targets {
+        let mut block = (t, 1);
+        while let Some(last) = blocks.last()
+            && last.0 > block.0
+        {
+            let size = last.1 + block.1;
+            block = (
+                (last.0 * last.1 as f32 + block.0 * block.1 as f32) / size as f32,
+                size,
+            );
+            blocks.pop();
+        }
+        blocks.push(block);
+    }
+
+    blocks
+        .into_iter()
+        .flat_map(|(mean, size)| std::iter::repeat_n(mean, size))
+        .enumerate()
+        .map(|(i, y)| y + i as f32 * spacing)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use emath::Pos2;
+    use ulid::Ulid;
+
+    use super::{HierarchicalLayout, acyclic_edges, assign_layers, count_crossings, spaced_fit};
+    use crate::{Graph, OrientedGraph, Real};
+
+    /// Graph whose nodes have the ids `1..=n`, so that their order is known
+    fn graph(n: u128, edges: &[(u128, u128)]) -> (OrientedGraph<Pos2>, Vec<Ulid>) {
+        let mut graph = OrientedGraph::empty();
+        let ids: Vec<Ulid> = (1..=n).map(Ulid::from).collect();
+        for id in ids.iter() {
+            graph.nodes.insert(*id, Pos2::ZERO);
+        }
+        for (from, to) in edges {
+            graph
+                .link(&Ulid::from(*from), &Ulid::from(*to), Real(1.0))
+                .unwrap();
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn cycles_are_broken() {
+        let (graph, ids) = graph(4, &[(1, 2), (2, 3), (3, 1), (3, 4), (4, 3), (2, 2)]);
+        let edges = acyclic_edges(&graph, &ids);
+        // The self-loop is dropped, and both directions between 3 and 4 are merged
+        assert_eq!(edges.len(), 4);
+
+        let layers = assign_layers(&ids, &edges);
+        assert!(edges.iter().all(|(from, to)| layers[from] < layers[to]));
+    }
+
+    #[test]
+    fn longest_path_layering() {
+        let (graph, ids) = graph(4, &[(1, 2), (2, 3), (1, 3), (4, 3)]);
+        let layers = assign_layers(&ids, &acyclic_edges(&graph, &ids));
+        let layer = |i: u128| layers[&Ulid::from(i)];
+        assert_eq!((layer(1), layer(2), layer(3), layer(4)), (0, 1, 2, 0));
+    }
+
+    #[test]
+    fn crossings_between_layers() {
+        let below = vec![vec![3], vec![2], vec![], vec![]];
+        assert_eq!(count_crossings(&[vec![0, 1], vec![2, 3]], &below), 1);
+        assert_eq!(count_crossings(&[vec![0, 1], vec![3, 2]], &below), 0);
+
+        // Edges of K(3, 3) cross once for each pair of sources and pair of targets
+        let below = vec![vec![3, 4, 5]; 3];
+        assert_eq!(count_crossings(&[vec![0, 1, 2], vec![3, 4, 5]], &below), 9);
+    }
+
+    #[test]
+    fn spaced_fit_keeps_order_and_spacing() {
+        assert_eq!(spaced_fit(&[0.0, 0.0, 0.0], 10.0), [-10.0, 0.0, 10.0]);
+        assert_eq!(spaced_fit(&[0.0, 50.0], 10.0), [0.0, 50.0]);
+        assert_eq!(spaced_fit(&[10.0, 0.0], 10.0), [0.0, 10.0]);
+    }
+
+    #[test]
+    fn layout_removes_crossings_and_routes_long_edges() {
+        let (mut graph, _) = graph(5, &[(1, 4), (2, 3), (4, 5), (1, 5)]);
+        let layout = HierarchicalLayout::default();
+        let routes = layout.apply(&mut graph);
+        let pos = |i: u128| graph.nodes[&Ulid::from(i)];
+
+        // Edges go down one layer at a time, except for 1 -> 5 which goes through a bend
+        for (from, to) in [(1, 4), (2, 3), (4, 5)] {
+            assert_eq!(pos(to).y - pos(from).y, layout.layer_spacing);
+        }
+        let bends = &routes[&(Ulid::from(1), Ulid::from(5))];
+        assert_eq!(bends.len(), 1);
+        assert_eq!(bends[0].y, pos(4).y);
+        assert_eq!(routes.len(), 1);
+
+        // 1 and 2 are in the first layer, their edges don't cross
+        assert_eq!(pos(1).x < pos(2).x, pos(4).x < pos(3).x);
+        assert!((pos(1).x - pos(2).x).abs() >= layout.node_spacing);
+    }
+}
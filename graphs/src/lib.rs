@@ -0,0 +1,229 @@
+//! Directed graphs whose nodes carry any payload, along with algorithms to analyse, generate and
+//! lay them out. Nothing here depends on a user interface : the editor is built on top of this
+//! crate, but graphs can just as well be used headless.
+//!
+//! ```
+//! use graphs::{Graph, GraphError, OrientedGraph, Real};
+//!
+//! // Nodes can carry any data, here their name
+//! let mut graph: OrientedGraph<&str> = OrientedGraph::empty();
+//! let paris = graph.insert("Paris");
+//! let lyon = graph.insert("Lyon");
+//! let marseille = graph.insert("Marseille");
+//! graph.link(&paris, &lyon, Real(465.0))?;
+//! graph.link(&lyon, &marseille, Real(315.0))?;
+//! graph.link(&paris, &marseille, Real(900.0))?;
+//!
+//! let (path, distance) = graph.dijkstra(&paris, &marseille)?.unwrap();
+//! assert_eq!(path, [paris, lyon, marseille]);
+//! assert_eq!(distance, Real(780.0));
+//!
+//! // Operations on nodes that aren't in the graph fail
+//! graph.remove(&lyon)?;
+//! assert_eq!(
+//!     graph.link(&paris, &lyon, Real(465.0)),
+//!     Err(GraphError::NodeNotFound(lyon))
+//! );
+//! # Ok::<(), GraphError>(())
+//! ```
+//!
+//! Layouts, generators and classic graphs need to place the nodes, which is possible as soon as
+//! their payload is [`Spatial`] :
+//!
+//! ```
+//! use graphs::{Graph, OrientedGraph, emath::Pos2, layout::ForceLayout};
+//!
+//! let mut graph: OrientedGraph<Pos2> = OrientedGraph::petersen();
+//! assert_eq!(graph.edge_count(), 30);
+//!
+//! // No node is pinned in place
+//! ForceLayout::default().run(&mut graph, &Default::default(), 100);
+//! assert!(graph.nodes.values().all(|p| p.x.is_finite() && p.y.is_finite()));
+//! ```
+//!
+//! With the `parallel` feature, centrality, all-pairs shortest paths and the largest random
+//! generators run on all cores, seeded generators still giving the same graphs.
+//!
+//! The algorithms which may take long have a `_with_progress` variant, reporting how far they
+//! got to a [`Progress`] which can cancel them.
+
+use std::{collections::HashMap, fmt::Debug, ops::Add};
+
+pub use ulid::Ulid;
+
+pub mod attributes;
+pub mod centrality;
+pub mod classic;
+pub mod cliques;
+pub mod csr;
+pub mod error;
+pub mod generators;
+pub mod layout;
+pub mod metrics;
+pub mod node;
+pub mod operations;
+pub mod oriented;
+mod parallel;
+pub mod progress;
+pub mod search;
+pub mod spectral;
+pub mod trace;
+pub mod weight;
+pub use attributes::{AttributeValue, Attributes};
+pub use centrality::DistanceMatrix;
+pub use csr::Csr;
+pub use error::{GraphError, Result};
+pub use metrics::{DegreeStats, GraphMetrics};
+pub use node::Spatial;
+pub use operations::Product;
+pub use oriented::OrientedGraph;
+pub use progress::Progress;
+pub use search::{Direction, EdgeKind, Search, Visitor};
+pub use spectral::{Eigen, GraphMatrix, MatrixKind};
+pub use trace::{TraceEvent, TraceState, Traversal};
+pub use weight::Real;
+
+pub use emath;
+
+/// Bounds required from edge weights, implemented for every type satisfying them
+pub trait GraphWeight:
+    Ord + PartialOrd + PartialEq + Default + Clone + Add<Output = Self> + Debug + Send + Sync
+{
+}
+impl<T> GraphWeight for T where
+    T: Ord + PartialOrd + PartialEq + Default + Clone + Add<Output = Self> + Debug + Send + Sync
+{
+}
+
+/// Directed graph whose nodes carry a payload of type `N`, and whose edges are weighted by `W`.
+/// Nothing here depends on how the graph is displayed, the payload can be anything
+pub trait Graph<N, W = Real>
+where
+    W: GraphWeight,
+{
+    fn new(nodes: HashMap<Ulid, N>, edges: HashMap<(Ulid, Ulid), W>) -> Self;
+    fn empty() -> Self;
+
+    fn node_count(&self) -> usize;
+    fn edge_count(&self) -> usize;
+
+    fn clear(&mut self);
+    fn insert(&mut self, node: N) -> Ulid;
+    /// Inserts a node along with edges between existing nodes, failing without modifying the
+    /// graph if one of them doesn't exist
+    fn insert_with_edges(
+        &mut self,
+        node: N,
+        edges: impl IntoIterator<Item = ((Ulid, Ulid), W)>,
+    ) -> Result<Ulid>;
+    /// Removes a node and all its edges, returning its payload
+    fn remove(&mut self, node: &Ulid) -> Result<N>;
+
+    /// Creates or replaces the edge Node1 -> Node2, both nodes having to exist
+    fn link(&mut self, node1: &Ulid, node2: &Ulid, weight: W) -> Result<()>;
+
+    /// Iterates over the nodes along with their payloads
+    fn nodes<'a>(&'a self) -> impl Iterator<Item = (&'a Ulid, &'a N)>
+    where
+        N: 'a;
+    /// Iterates over the edges, as `(from, to, weight)`
+    fn edges<'a>(&'a self) -> impl Iterator<Item = (&'a Ulid, &'a Ulid, &'a W)>
+    where
+        W: 'a;
+    /// Iterates over the edges leaving `node`, as `(to, weight)`. [`OrientedGraph`] goes
+    /// through all its edges on each call : algorithms looking at the neighbors of many nodes
+    /// should build a [`Csr`] once instead
+    fn out_edges<'a>(&'a self, node: &Ulid) -> impl Iterator<Item = (&'a Ulid, &'a W)>
+    where
+        W: 'a;
+    /// Iterates over the edges entering `node`, as `(from, weight)`. Same cost as
+    /// [`Graph::out_edges`]
+    fn in_edges<'a>(&'a self, node: &Ulid) -> impl Iterator<Item = (&'a Ulid, &'a W)>
+    where
+        W: 'a;
+
+    fn out_degree(&self, node: &Ulid) -> usize {
+        self.out_edges(node).count()
+    }
+    fn in_degree(&self, node: &Ulid) -> usize {
+        self.in_edges(node).count()
+    }
+    /// Number of edges touching `node`, a self-loop counting twice
+    fn degree(&self, node: &Ulid) -> usize {
+        self.out_degree(node) + self.in_degree(node)
+    }
+
+    /// Owned version of [`Graph::in_edges`]
+    fn neighbors_in(&self, node: &Ulid) -> Vec<(Ulid, W)> {
+        self.in_edges(node).map(|(n, w)| (*n, w.clone())).collect()
+    }
+    /// Owned version of [`Graph::out_edges`]
+    fn neighbors_out(&self, node: &Ulid) -> Vec<(Ulid, W)> {
+        self.out_edges(node).map(|(n, w)| (*n, w.clone())).collect()
+    }
+
+    fn contains(&self, node: &Ulid) -> bool;
+    /// Checks if a link Node1 -> Node2 exists
+    fn linked(&self, node1: &Ulid, node2: &Ulid) -> bool;
+
+    /// Breadth-first search from each of the `starts` not reached yet, calling the hooks of
+    /// `visitor` along the way. Fails if one of the starts isn't in the graph
+    ///
+    /// ```
+    /// use graphs::{Direction, EdgeKind, Graph, OrientedGraph, Real, Ulid, Visitor};
+    ///
+    /// let mut graph: OrientedGraph<()> = OrientedGraph::empty();
+    /// let [a, b, c] = [(), (), ()].map(|n| graph.insert(n));
+    /// graph.link(&a, &b, Real(1.0))?;
+    /// graph.link(&b, &c, Real(1.0))?;
+    /// graph.link(&c, &a, Real(1.0))?;
+    ///
+    /// struct BackEdges(Vec<(Ulid, Ulid)>);
+    /// impl Visitor for BackEdges {
+    ///     fn edge(&mut self, from: &Ulid, to: &Ulid, kind: EdgeKind) {
+    ///         if kind == EdgeKind::Back {
+    ///             self.0.push((*from, *to));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut back = BackEdges(Vec::new());
+    /// let search = graph.bfs([a], Direction::Outgoing, &mut back)?;
+    /// assert_eq!(search.order, [a, b, c]);
+    /// assert_eq!(search.depths[&c], 2);
+    /// assert_eq!(back.0, [(c, a)]);
+    ///
+    /// // Following the edges backwards
+    /// let search = graph.bfs([a], Direction::Incoming, &mut ())?;
+    /// assert_eq!(search.parents[&b], c);
+    /// # Ok::<(), graphs::GraphError>(())
+    /// ```
+    fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = Ulid>,
+        direction: Direction,
+        visitor: &mut impl Visitor,
+    ) -> Result<Search>
+    where
+        Self: Sized,
+    {
+        search::bfs(self, starts, direction, visitor)
+    }
+
+    /// Depth-first search from each of the `starts` not reached yet, calling the hooks of
+    /// `visitor` along the way. Fails if one of the starts isn't in the graph
+    fn dfs(
+        &self,
+        starts: impl IntoIterator<Item = Ulid>,
+        direction: Direction,
+        visitor: &mut impl Visitor,
+    ) -> Result<Search>
+    where
+        Self: Sized,
+    {
+        search::dfs(self, starts, direction, visitor)
+    }
+    /// Tries to find a path from `start` to `end`, with the smallest weight possible. Returns the path, along with its total weight,
+    /// or `None` if `end` can't be reached
+    fn dijkstra(&self, start: &Ulid, end: &Ulid) -> Result<Option<(Vec<Ulid>, W)>>;
+}
@@ -0,0 +1,411 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use ulid::Ulid;
+
+use crate::{Csr, Graph, GraphWeight, OrientedGraph, Progress, Result, progress::Steps};
+
+#[derive(Debug, Default, Clone)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub average: f32,
+}
+
+/// Statistics about the structure of a graph
+#[derive(Debug, Default, Clone)]
+pub struct GraphMetrics {
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Ratio of existing edges over all the possible ones (self-loops excluded)
+    pub density: f32,
+    pub in_degree: DegreeStats,
+    pub out_degree: DegreeStats,
+    /// Greatest eccentricity. `None` if the graph isn't strongly connected
+    pub diameter: Option<usize>,
+    /// Smallest eccentricity, among the nodes which can reach all others
+    pub radius: Option<usize>,
+    /// Nodes whose eccentricity is the radius
+    pub center: Vec<Ulid>,
+    /// Nodes whose eccentricity is the diameter
+    pub periphery: Vec<Ulid>,
+    /// Length of the shortest cycle of the underlying simple undirected graph, if there is one
+    pub girth: Option<usize>,
+    /// Length of the shortest directed cycle, if there is one. Reciprocal edges form cycles of
+    /// length 2, so this is 2 for any undirected graph with an edge
+    pub directed_girth: Option<usize>,
+    /// Number of triangles in the underlying undirected graph
+    pub triangles: usize,
+    /// Average local clustering coefficient of the underlying undirected graph
+    pub clustering: f32,
+    /// Number of nodes for each total (in + out) degree
+    pub degree_distribution: Vec<usize>,
+}
+
+impl DegreeStats {
+    fn from_degrees(degrees: impl Iterator<Item = usize>) -> Self {
+        let mut stats = DegreeStats {
+            min: usize::MAX,
+            ..Default::default()
+        };
+        let mut count = 0;
+        let mut sum = 0;
+
+        for d in degrees {
+            stats.min = stats.min.min(d);
+            stats.max = stats.max.max(d);
+            sum += d;
+            count += 1;
+        }
+
+        if count == 0 {
+            stats.min = 0;
+        } else {
+            stats.average = sum as f32 / count as f32;
+        }
+        stats
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    /// Computes all the statistics of the graph. Distances are counted in number of edges,
+    /// following edge directions
+    ///
+    /// ```
+    /// use graphs::{OrientedGraph, emath::Pos2};
+    ///
+    /// let cycle: OrientedGraph<Pos2> = OrientedGraph::cycle(6);
+    /// let metrics = cycle.metrics();
+    /// assert_eq!(metrics.diameter, Some(3));
+    /// assert_eq!(metrics.triangles, 0);
+    /// assert_eq!(metrics.girth, Some(6));
+    /// assert_eq!(metrics.directed_girth, Some(2));
+    /// ```
+    pub fn metrics(&self) -> GraphMetrics {
+        // Never cancelled
+        self.metrics_with_progress(&|_| true).unwrap_or_default()
+    }
+
+    /// Computes all the statistics of the graph, the progress being counted in breadth-first
+    /// searches : one from each node for the eccentricities, and as many for each girth
+    pub fn metrics_with_progress(&self, progress: &impl Progress) -> Result<GraphMetrics> {
+        let n = self.node_count();
+        let steps = Steps::new(progress, 3 * n);
+        let successors = self.successors();
+
+        let mut in_degrees: HashMap<Ulid, usize> = self.nodes.keys().map(|id| (*id, 0)).collect();
+        for (_, to) in self.edges.keys() {
+            if let Some(d) = in_degrees.get_mut(to) {
+                *d += 1;
+            }
+        }
+
+        let mut degree_distribution = Vec::new();
+        for (id, out) in successors.iter() {
+            let degree = out.len() + in_degrees[id];
+            if degree_distribution.len() <= degree {
+                degree_distribution.resize(degree + 1, 0);
+            }
+            degree_distribution[degree] += 1;
+        }
+
+        // Eccentricities, computed with a BFS from each node
+        let csr = Csr::from_graph(self);
+        let mut eccentricities: HashMap<Ulid, Option<usize>> = HashMap::new();
+        for i in 0..n {
+            let distances: Option<Vec<usize>> = csr.bfs_distances(i).into_iter().collect();
+            eccentricities.insert(csr.id(i), distances.and_then(|d| d.into_iter().max()));
+            if !steps.step() {
+                return steps.finish(GraphMetrics::default());
+            }
+        }
+
+        let diameter = if eccentricities.values().all(|e| e.is_some()) {
+            eccentricities.values().flatten().max().copied()
+        } else {
+            None
+        };
+        let radius = eccentricities.values().flatten().min().copied();
+
+        let with_eccentricity = |target: Option<usize>| -> Vec<Ulid> {
+            eccentricities
+                .iter()
+                .filter(|(_, e)| target.is_some() && **e == target)
+                .map(|(id, _)| *id)
+                .collect()
+        };
+
+        let girth = self.girth_steps(&steps);
+        let directed_girth = self.directed_girth_steps(&csr, &steps);
+        let (triangles, clustering) = self.triangles_and_clustering();
+
+        steps.finish(GraphMetrics {
+            node_count: n,
+            edge_count: self.edge_count(),
+            density: if n > 1 {
+                self.edges.keys().filter(|(a, b)| a != b).count() as f32 / (n * (n - 1)) as f32
+            } else {
+                0.0
+            },
+            in_degree: DegreeStats::from_degrees(in_degrees.values().copied()),
+            out_degree: DegreeStats::from_degrees(successors.values().map(|s| s.len())),
+            diameter,
+            radius,
+            center: with_eccentricity(radius),
+            periphery: with_eccentricity(diameter),
+            girth,
+            directed_girth,
+            triangles,
+            clustering,
+            degree_distribution,
+        })
+    }
+
+    /// Length of the shortest cycle of the underlying simple undirected graph, found with a BFS
+    /// from each node : the first edge leading to an already reached node, other than the one
+    /// to its parent, closes a cycle
+    pub fn girth(&self) -> Option<usize> {
+        self.girth_steps(&Steps::new(&|_| true, self.node_count()))
+    }
+
+    /// Length of the shortest directed cycle of the graph, self-loops counting as cycles of
+    /// length 1
+    pub fn directed_girth(&self) -> Option<usize> {
+        let steps = Steps::new(&|_| true, self.node_count());
+        self.directed_girth_steps(&Csr::from_graph(self), &steps)
+    }
+
+    /// Girth, stopping early if one of the searches is cancelled
+    fn girth_steps(&self, steps: &Steps) -> Option<usize> {
+        let adjacency = self.undirected_adjacency();
+        let mut girth: Option<usize> = None;
+
+        for start in adjacency.keys() {
+            if !steps.step() {
+                return None;
+            }
+            let mut depths = HashMap::from([(*start, 0)]);
+            let mut parents: HashMap<Ulid, Ulid> = HashMap::new();
+            let mut queue = VecDeque::from([*start]);
+
+            while let Some(node) = queue.pop_front() {
+                let d = depths[&node];
+                // Cycles found from here on can't be shorter
+                if girth.is_some_and(|g| 2 * d >= g) {
+                    break;
+                }
+
+                for next in adjacency[&node].iter() {
+                    if parents.get(&node) == Some(next) {
+                        continue;
+                    }
+                    match depths.get(next) {
+                        Some(d_next) => {
+                            let length = d + d_next + 1;
+                            if girth.is_none_or(|g| length < g) {
+                                girth = Some(length);
+                            }
+                        }
+                        None => {
+                            depths.insert(*next, d + 1);
+                            parents.insert(*next, node);
+                            queue.push_back(*next);
+                        }
+                    }
+                }
+            }
+        }
+
+        girth
+    }
+
+    /// Directed girth, stopping early if one of the searches is cancelled
+    fn directed_girth_steps(&self, csr: &Csr<W>, steps: &Steps) -> Option<usize> {
+        let mut girth: Option<usize> = None;
+
+        for start in 0..csr.node_count() {
+            if !steps.step() {
+                return None;
+            }
+            let distances = csr.bfs_distances(start);
+
+            // Any edge going back to the start closes a cycle
+            for (node, d) in distances.iter().enumerate() {
+                if let Some(d) = d
+                    && csr.successors(node).binary_search(&start).is_ok()
+                    && girth.is_none_or(|g| d + 1 < g)
+                {
+                    girth = Some(d + 1);
+                }
+            }
+        }
+
+        girth
+    }
+
+    /// Number of triangles, and average local clustering coefficient of the underlying
+    /// undirected graph
+    pub fn triangles_and_clustering(&self) -> (usize, f32) {
+        let adjacency = self.undirected_adjacency();
+        let mut triangles = 0;
+        let mut clustering = 0.0;
+
+        for neighbors in adjacency.values() {
+            let k = neighbors.len();
+            if k < 2 {
+                continue;
+            }
+
+            // Count links between neighbors
+            let links: usize = neighbors
+                .iter()
+                .map(|n| adjacency[n].intersection(neighbors).count())
+                .sum::<usize>()
+                / 2;
+
+            triangles += links;
+            clustering += links as f32 / (k * (k - 1) / 2) as f32;
+        }
+
+        // Each triangle is counted once per node
+        (
+            triangles / 3,
+            if adjacency.is_empty() {
+                0.0
+            } else {
+                clustering / adjacency.len() as f32
+            },
+        )
+    }
+
+    /// Builds the list of successors of each node, ignoring edges to unknown nodes
+    fn successors(&self) -> HashMap<Ulid, HashSet<Ulid>> {
+        let mut successors: HashMap<Ulid, HashSet<Ulid>> =
+            self.nodes.keys().map(|id| (*id, HashSet::new())).collect();
+
+        for (from, to) in self.edges.keys() {
+            if self.nodes.contains_key(to)
+                && let Some(s) = successors.get_mut(from)
+            {
+                s.insert(*to);
+            }
+        }
+
+        successors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use emath::Pos2;
+    use ulid::Ulid;
+
+    use crate::{Graph, OrientedGraph, Real};
+
+    type G = OrientedGraph<Pos2>;
+
+    #[test]
+    fn girth_of_undirected_graphs() {
+        assert_eq!(G::cycle(6).girth(), Some(6));
+        assert_eq!(G::complete(4).girth(), Some(3));
+        assert_eq!(G::grid(3, 4).girth(), Some(4));
+        assert_eq!(G::complete_bipartite(2, 3).girth(), Some(4));
+        assert_eq!(G::petersen().girth(), Some(5));
+        assert_eq!(G::path(5).girth(), None);
+        assert_eq!(G::star(4).girth(), None);
+    }
+
+    #[test]
+    fn directed_girth() {
+        assert_eq!(G::cycle(6).directed_girth(), Some(2));
+
+        let mut graph = G::empty();
+        let [a, b, c] = [(); 3].map(|_| graph.insert(Pos2::ZERO));
+        graph.link(&a, &b, Real(1.0)).unwrap();
+        graph.link(&b, &c, Real(1.0)).unwrap();
+        assert_eq!(graph.directed_girth(), None);
+        // Not a directed cycle, but still a triangle once directions are ignored
+        graph.link(&a, &c, Real(1.0)).unwrap();
+        assert_eq!(graph.directed_girth(), None);
+        assert_eq!(graph.girth(), Some(3));
+
+        graph.link(&c, &a, Real(1.0)).unwrap();
+        assert_eq!(graph.directed_girth(), Some(2));
+        graph.link(&b, &b, Real(1.0)).unwrap();
+        assert_eq!(graph.directed_girth(), Some(1));
+    }
+
+    #[test]
+    fn complete_graph() {
+        let metrics = G::complete(4).metrics();
+        assert_eq!((metrics.node_count, metrics.edge_count), (4, 12));
+        assert_eq!(metrics.triangles, 4);
+        assert_eq!(metrics.clustering, 1.0);
+        assert_eq!(metrics.density, 1.0);
+        assert_eq!((metrics.diameter, metrics.radius), (Some(1), Some(1)));
+        assert_eq!(metrics.center.len(), 4);
+    }
+
+    #[test]
+    fn petersen() {
+        let metrics = G::petersen().metrics();
+        assert_eq!((metrics.diameter, metrics.radius), (Some(2), Some(2)));
+        assert_eq!(metrics.triangles, 0);
+        assert_eq!(metrics.clustering, 0.0);
+        assert_eq!(metrics.girth, Some(5));
+        assert_eq!(metrics.density, 1.0 / 3.0);
+        assert_eq!((metrics.out_degree.min, metrics.out_degree.max), (3, 3));
+        assert_eq!(metrics.in_degree.average, 3.0);
+        // All nodes have 3 incoming and 3 outgoing edges
+        let mut distribution = vec![0; 7];
+        distribution[6] = 10;
+        assert_eq!(metrics.degree_distribution, distribution);
+    }
+
+    #[test]
+    fn center_and_periphery_of_a_path() {
+        let mut graph = G::empty();
+        let ids: Vec<Ulid> = (0..5).map(|_| graph.insert(Pos2::ZERO)).collect();
+        for pair in ids.windows(2) {
+            graph
+                .link_undirected(&pair[0], &pair[1], Real(1.0))
+                .unwrap();
+        }
+
+        let metrics = graph.metrics();
+        assert_eq!((metrics.diameter, metrics.radius), (Some(4), Some(2)));
+        assert_eq!(metrics.center, vec![ids[2]]);
+        assert_eq!(
+            metrics.periphery.into_iter().collect::<HashSet<_>>(),
+            HashSet::from([ids[0], ids[4]])
+        );
+    }
+
+    #[test]
+    fn degrees_of_a_star() {
+        let metrics = G::star(4).metrics();
+        assert_eq!((metrics.out_degree.min, metrics.out_degree.max), (1, 4));
+        assert_eq!(metrics.in_degree.average, 8.0 / 5.0);
+        // The leaves have a total degree of 2, the center of 8
+        assert_eq!(metrics.degree_distribution, vec![0, 0, 4, 0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn disconnected_graph() {
+        let triangle = G::complete(3);
+        let metrics = triangle.disjoint_union(&triangle).metrics();
+        assert_eq!(metrics.triangles, 2);
+        assert_eq!(metrics.diameter, None);
+        assert_eq!(metrics.radius, None);
+        assert!(metrics.center.is_empty() && metrics.periphery.is_empty());
+
+        let empty = G::empty().metrics();
+        assert_eq!((empty.node_count, empty.diameter), (0, None));
+        assert_eq!(empty.out_degree.min, 0);
+        assert!(empty.degree_distribution.is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+use emath::Pos2;
+
+/// Node payload placed in the plane. Graphs whose nodes implement it can be laid out, and built
+/// by the generators and the classic constructors, which position the nodes they create
+pub trait Spatial: Default {
+    fn pos(&self) -> Pos2;
+    fn set_pos(&mut self, pos: Pos2);
+
+    /// Default payload at the given position
+    fn at_pos(pos: Pos2) -> Self {
+        let mut node = Self::default();
+        node.set_pos(pos);
+        node
+    }
+}
+
+// Positions are enough for graphs that only need to be laid out
+impl Spatial for Pos2 {
+    fn pos(&self) -> Pos2 {
+        *self
+    }
+
+    fn set_pos(&mut self, pos: Pos2) {
+        *self = pos;
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::{HashMap, HashSet};
+
+use ulid::Ulid;
+
+use crate::{Csr, Graph, GraphError, GraphWeight, OrientedGraph, Result};
+
+/// Ways of combining two graphs over the pairs of their nodes
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Product {
+    /// `(u, v) -> (u', v')` if `u = u'` and `v -> v'`, or `v = v'` and `u -> u'`
+    #[default]
+    Cartesian,
+    /// `(u, v) -> (u', v')` if `u -> u'` and `v -> v'`
+    Tensor,
+    /// Union of the Cartesian and tensor products
+    Strong,
+}
+
+impl Product {
+    pub const ALL: [Product; 3] = [Product::Cartesian, Product::Tensor, Product::Strong];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Product::Cartesian => "Cartesian",
+            Product::Tensor => "Tensor",
+            Product::Strong => "Strong",
+        }
+    }
+}
+
+/// Operations building new graphs out of existing ones. Unless stated otherwise, created
+/// edges have a default weight and no attributes
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Clone,
+    W: GraphWeight,
+{
+    /// Same nodes, linked by the edges missing from this graph (self-loops excluded)
+    pub fn complement(&self) -> Self {
+        let mut complement = Self::new(self.nodes.clone(), HashMap::new());
+
+        for from in self.nodes.keys() {
+            for to in self.nodes.keys() {
+                if from != to && !self.linked(from, to) {
+                    complement.edges.insert((*from, *to), W::default());
+                }
+            }
+        }
+
+        complement
+    }
+
+    /// Same graph, with every edge reversed along with its weight and attributes
+    pub fn transpose(&self) -> Self {
+        fn reversed<T>(((from, to), value): ((Ulid, Ulid), T)) -> ((Ulid, Ulid), T) {
+            ((to, from), value)
+        }
+
+        OrientedGraph {
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone().into_iter().map(reversed).collect(),
+            edge_attributes: self
+                .edge_attributes
+                .clone()
+                .into_iter()
+                .map(reversed)
+                .collect(),
+        }
+    }
+
+    /// Nodes and edges of both graphs, those of this one taking precedence when both graphs
+    /// have them
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = other.clone();
+        union.nodes.extend(self.nodes.clone());
+        union.edges.extend(self.edges.clone());
+        union.edge_attributes.extend(self.edge_attributes.clone());
+        union
+    }
+
+    /// Both graphs side by side, the nodes of `other` getting new ids
+    pub fn disjoint_union(&self, other: &Self) -> Self {
+        let ids: HashMap<Ulid, Ulid> = other.nodes.keys().map(|id| (*id, Ulid::new())).collect();
+        let mut union = self.clone();
+
+        union
+            .nodes
+            .extend(other.nodes.iter().map(|(id, node)| (ids[id], node.clone())));
+        for (edge, w) in other.edges.iter() {
+            if let (Some(from), Some(to)) = (ids.get(&edge.0), ids.get(&edge.1)) {
+                union.edges.insert((*from, *to), w.clone());
+                if let Some(attributes) = other.edge_attributes.get(edge) {
+                    union
+                        .edge_attributes
+                        .insert((*from, *to), attributes.clone());
+                }
+            }
+        }
+
+        union
+    }
+
+    /// Nodes and edges found in both graphs, as they are in this one
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Self::empty();
+        intersection.nodes = self
+            .nodes
+            .iter()
+            .filter(|(id, _)| other.nodes.contains_key(id))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect();
+
+        for (edge, w) in self.edges.iter() {
+            if other.edges.contains_key(edge)
+                && intersection.nodes.contains_key(&edge.0)
+                && intersection.nodes.contains_key(&edge.1)
+            {
+                intersection.edges.insert(*edge, w.clone());
+                if let Some(attributes) = self.edge_attributes.get(edge) {
+                    intersection
+                        .edge_attributes
+                        .insert(*edge, attributes.clone());
+                }
+            }
+        }
+
+        intersection
+    }
+
+    /// Product of both graphs, whose nodes are the pairs of their nodes, their payload being
+    /// built by `combine`. Edges coming from a single edge of a factor keep its weight, those
+    /// coming from an edge of each factor get the sum of both weights
+    ///
+    /// ```
+    /// use graphs::{Graph, OrientedGraph, Product, emath::Pos2};
+    ///
+    /// // The Cartesian product of two paths is a grid
+    /// let path: OrientedGraph<Pos2> = OrientedGraph::path(3);
+    /// let grid = path.product(&path, Product::Cartesian, |a, b| *a + b.to_vec2());
+    /// assert_eq!(grid.node_count(), 9);
+    /// // 12 undirected edges
+    /// assert_eq!(grid.edge_count(), 24);
+    /// ```
+    pub fn product(&self, other: &Self, kind: Product, combine: impl Fn(&N, &N) -> N) -> Self {
+        let mut product = Self::empty();
+        let mut ids: HashMap<(Ulid, Ulid), Ulid> = HashMap::new();
+
+        for (u, a) in self.nodes.iter() {
+            for (v, b) in other.nodes.iter() {
+                ids.insert((*u, *v), product.insert(combine(a, b)));
+            }
+        }
+        let mut link = |from: (Ulid, Ulid), to: (Ulid, Ulid), w: W| {
+            if let (Some(from), Some(to)) = (ids.get(&from), ids.get(&to)) {
+                product.edges.insert((*from, *to), w);
+            }
+        };
+
+        if kind != Product::Tensor {
+            for ((u, u2), w) in self.edges.iter() {
+                for v in other.nodes.keys() {
+                    link((*u, *v), (*u2, *v), w.clone());
+                }
+            }
+            for ((v, v2), w) in other.edges.iter() {
+                for u in self.nodes.keys() {
+                    link((*u, *v), (*u, *v2), w.clone());
+                }
+            }
+        }
+        if kind != Product::Cartesian {
+            for ((u, u2), w1) in self.edges.iter() {
+                for ((v, v2), w2) in other.edges.iter() {
+                    link((*u, *v), (*u2, *v2), w1.clone() + w2.clone());
+                }
+            }
+        }
+
+        product
+    }
+
+    /// Graph whose nodes are the edges of this one, `a -> b` being linked to `b -> c`. The
+    /// payloads are built by `node` from those of the ends of the edges
+    pub fn line_graph(&self, node: impl Fn(&N, &N) -> N) -> Self {
+        let mut line = Self::empty();
+        let ids: HashMap<(Ulid, Ulid), Ulid> = self
+            .edges
+            .keys()
+            .filter_map(|(from, to)| {
+                let payload = node(self.nodes.get(from)?, self.nodes.get(to)?);
+                Some(((*from, *to), line.insert(payload)))
+            })
+            .collect();
+
+        let csr = Csr::from_graph(self);
+        for ((from, to), id) in ids.iter() {
+            for next in csr.successors(csr.index(to).unwrap()) {
+                let next = csr.id(*next);
+                if let Some(next_id) = ids.get(&(*to, next))
+                    && (*to, next) != (*from, *to)
+                {
+                    line.edges.insert((*id, *next_id), W::default());
+                }
+            }
+        }
+
+        line
+    }
+
+    /// Same nodes, each one linked to those it reaches through at most `k` edges. Existing
+    /// edges keep their weight and attributes
+    pub fn power(&self, k: usize) -> Self {
+        let mut power = self.clone();
+        let csr = Csr::from_graph(self);
+
+        for i in 0..csr.node_count() {
+            for (j, d) in csr.bfs_distances(i).into_iter().enumerate() {
+                if i != j && d.is_some_and(|d| d <= k) {
+                    power.edges.entry((csr.id(i), csr.id(j))).or_default();
+                }
+            }
+        }
+
+        power
+    }
+
+    /// Merges `nodes` into a single new node carrying `merged`, which takes over their edges
+    /// with the rest of the graph. Edges between merged nodes disappear, and when several
+    /// edges end up between the same nodes, the lightest one is kept. Returns the new graph
+    /// along with the id of the merged node
+    pub fn contract(&self, nodes: &HashSet<Ulid>, merged: N) -> Result<(Self, Ulid)> {
+        self.check_nodes(nodes)?;
+        if nodes.len() < 2 {
+            return Err(GraphError::NotEnoughNodes {
+                required: 2,
+                found: nodes.len(),
+            });
+        }
+
+        let mut contracted = Self::empty();
+        contracted.nodes = self
+            .nodes
+            .iter()
+            .filter(|(id, _)| !nodes.contains(id))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect();
+        let id = contracted.insert(merged);
+        let replace = |node: &Ulid| if nodes.contains(node) { id } else { *node };
+
+        for ((from, to), w) in self.edges.iter() {
+            let edge = (replace(from), replace(to));
+            if nodes.contains(from) && nodes.contains(to) {
+                continue;
+            }
+
+            if edge == (*from, *to) {
+                if let Some(attributes) = self.edge_attributes.get(&edge) {
+                    contracted.edge_attributes.insert(edge, attributes.clone());
+                }
+                contracted.edges.insert(edge, w.clone());
+            } else if contracted
+                .edges
+                .get(&edge)
+                .is_none_or(|current| w < current)
+            {
+                contracted.edges.insert(edge, w.clone());
+            }
+        }
+
+        Ok((contracted, id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use emath::Pos2;
+    use rand::{SeedableRng, rngs::StdRng};
+    use ulid::Ulid;
+
+    use super::Product;
+    use crate::{AttributeValue, Attributes, Graph, GraphError, OrientedGraph, Real};
+
+    type G = OrientedGraph<Pos2>;
+
+    /// Graph with `n` nodes, linked by the given edges between their indices
+    fn graph(n: usize, edges: &[(usize, usize, f64)]) -> (G, Vec<Ulid>) {
+        let mut graph = G::empty();
+        let ids: Vec<Ulid> = (0..n).map(|_| graph.insert(Pos2::ZERO)).collect();
+        for (from, to, w) in edges {
+            graph.link(&ids[*from], &ids[*to], Real(*w)).unwrap();
+        }
+        (graph, ids)
+    }
+
+    fn edges(graph: &G) -> HashSet<(Ulid, Ulid)> {
+        graph.edges.keys().copied().collect()
+    }
+
+    #[test]
+    fn complement() {
+        assert_eq!(G::complete(5).complement().edge_count(), 0);
+
+        // The 5-cycle is its own complement
+        let complement = G::cycle(5).complement();
+        assert_eq!(complement.edge_count(), 10);
+        assert_eq!(complement.girth(), Some(5));
+
+        let graph = G::erdos_renyi_gnm(20, 60, &mut StdRng::seed_from_u64(7));
+        assert_eq!(edges(&graph.complement().complement()), edges(&graph));
+    }
+
+    #[test]
+    fn transpose_moves_weights_and_attributes() {
+        let (mut graph, ids) = graph(2, &[(0, 1, 3.0)]);
+        let attributes = Attributes::from([("label".to_string(), AttributeValue::Bool(true))]);
+        graph
+            .edge_attributes
+            .insert((ids[0], ids[1]), attributes.clone());
+
+        let transpose = graph.transpose();
+        assert_eq!(transpose.edges.len(), 1);
+        assert_eq!(transpose.edges[&(ids[1], ids[0])], Real(3.0));
+        assert_eq!(transpose.edge_attributes[&(ids[1], ids[0])], attributes);
+    }
+
+    #[test]
+    fn products() {
+        let edge = G::path(2);
+        let combine = |a: &Pos2, b: &Pos2| *a + b.to_vec2();
+
+        // K2 x K2 is a 4-cycle, K2 ⊗ K2 two disjoint edges and K2 ⊠ K2 is K4
+        let cartesian = edge.product(&edge, Product::Cartesian, combine);
+        assert_eq!((cartesian.node_count(), cartesian.edge_count()), (4, 8));
+        assert_eq!(cartesian.girth(), Some(4));
+        let tensor = edge.product(&edge, Product::Tensor, combine);
+        assert_eq!(tensor.edge_count(), 4);
+        assert_eq!(tensor.girth(), None);
+        let strong = edge.product(&edge, Product::Strong, combine);
+        assert_eq!(strong.edge_count(), 12);
+
+        // Edges coming from an edge of each factor weigh the sum of both
+        let (a, _) = graph(2, &[(0, 1, 2.0)]);
+        let (b, _) = graph(2, &[(0, 1, 5.0)]);
+        let tensor = a.product(&b, Product::Tensor, combine);
+        assert_eq!(tensor.edges.values().collect::<Vec<_>>(), [&Real(7.0)]);
+    }
+
+    #[test]
+    fn line_graph() {
+        // The line graph of a directed cycle is a directed cycle
+        let (cycle, _) = graph(4, &[(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0), (3, 0, 1.0)]);
+        let line = cycle.line_graph(|a, _| *a);
+        assert_eq!((line.node_count(), line.edge_count()), (4, 4));
+        assert!(line.nodes.keys().all(|id| line.out_degree(id) == 1));
+
+        // Edges leaving the same node aren't linked
+        let (star, _) = graph(4, &[(0, 1, 1.0), (0, 2, 1.0), (0, 3, 1.0)]);
+        assert_eq!(star.line_graph(|a, _| *a).edge_count(), 0);
+    }
+
+    #[test]
+    fn power() {
+        let (mut path, ids) = graph(5, &[]);
+        for i in 0..4 {
+            path.link(&ids[i], &ids[i + 1], Real(4.0)).unwrap();
+        }
+
+        let square = path.power(2);
+        assert_eq!(square.edge_count(), 4 + 3);
+        assert!(square.linked(&ids[0], &ids[2]));
+        assert!(!square.linked(&ids[0], &ids[3]));
+        assert!(!square.linked(&ids[2], &ids[0]));
+        // Existing edges keep their weight
+        assert_eq!(square.edges[&(ids[0], ids[1])], Real(4.0));
+
+        assert_eq!(path.power(4).edge_count(), 10);
+        assert_eq!(path.power(1).edge_count(), 4);
+    }
+
+    #[test]
+    fn contract() {
+        let (graph, ids) = graph(
+            4,
+            &[
+                (0, 1, 1.0),
+                (0, 2, 2.0),
+                (1, 2, 5.0),
+                (3, 0, 3.0),
+                (3, 1, 1.0),
+                (2, 3, 4.0),
+            ],
+        );
+        let merged = HashSet::from([ids[0], ids[1]]);
+        let (contracted, id) = graph.contract(&merged, Pos2::ZERO).unwrap();
+
+        assert_eq!(contracted.node_count(), 3);
+        assert!(!contracted.nodes.contains_key(&ids[0]));
+        // The lightest of the merged edges is kept, and the edge between merged nodes is gone
+        assert_eq!(contracted.edges.len(), 3);
+        assert_eq!(contracted.edges[&(id, ids[2])], Real(2.0));
+        assert_eq!(contracted.edges[&(ids[3], id)], Real(1.0));
+        assert_eq!(contracted.edges[&(ids[2], ids[3])], Real(4.0));
+
+        assert!(matches!(
+            graph.contract(&HashSet::from([ids[0]]), Pos2::ZERO),
+            Err(GraphError::NotEnoughNodes { .. })
+        ));
+        assert!(matches!(
+            graph.contract(&HashSet::from([ids[0], Ulid::new()]), Pos2::ZERO),
+            Err(GraphError::NodeNotFound(_))
+        ));
+    }
+}
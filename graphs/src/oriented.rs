@@ -0,0 +1,415 @@
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+
+use emath::{Rect, pos2};
+use ulid::Ulid;
+
+use crate::{
+    Attributes, Csr, Graph, GraphError, GraphWeight, Progress, Real, Result, Spatial,
+    layout::POINT_RADIUS, progress::Steps,
+};
+
+/// Directed graph stored as maps of its nodes' payloads and of its edges' weights
+#[derive(Debug, Clone)]
+pub struct OrientedGraph<N, W = Real>
+where
+    W: GraphWeight,
+{
+    pub nodes: HashMap<Ulid, N>,
+    pub edges: HashMap<(Ulid, Ulid), W>,
+    /// Custom attributes of the edges that have some
+    pub edge_attributes: HashMap<(Ulid, Ulid), Attributes>,
+}
+
+#[derive(PartialEq, Eq)]
+struct NodeState<W>
+where
+    W: GraphWeight,
+{
+    pub cost: W,
+    pub node: Ulid,
+}
+
+impl<W> Ord for NodeState<W>
+where
+    W: GraphWeight,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl<W> PartialOrd for NodeState<W>
+where
+    W: GraphWeight,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, W> Graph<N, W> for OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    fn new(nodes: HashMap<Ulid, N>, edges: HashMap<(Ulid, Ulid), W>) -> Self {
+        OrientedGraph {
+            nodes,
+            edges,
+            edge_attributes: HashMap::new(),
+        }
+    }
+
+    fn empty() -> Self {
+        OrientedGraph {
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            edge_attributes: HashMap::new(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.edge_attributes.clear();
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn insert(&mut self, vertex: N) -> Ulid {
+        let id = Ulid::new();
+        self.nodes.insert(id, vertex);
+        id
+    }
+
+    fn insert_with_edges(
+        &mut self,
+        vertex: N,
+        edges: impl IntoIterator<Item = ((Ulid, Ulid), W)>,
+    ) -> Result<Ulid> {
+        let edges: Vec<((Ulid, Ulid), W)> = edges.into_iter().collect();
+        for ((from, to), _) in edges.iter() {
+            self.check_node(from)?;
+            self.check_node(to)?;
+        }
+
+        self.edges.extend(edges);
+        Ok(self.insert(vertex))
+    }
+
+    fn remove(&mut self, node: &Ulid) -> Result<N> {
+        let payload = self
+            .nodes
+            .remove(node)
+            .ok_or(GraphError::NodeNotFound(*node))?;
+        self.edges.retain(|e, _| e.0 != *node && e.1 != *node);
+        self.edge_attributes
+            .retain(|e, _| e.0 != *node && e.1 != *node);
+        Ok(payload)
+    }
+
+    fn link(&mut self, v1: &Ulid, v2: &Ulid, weight: W) -> Result<()> {
+        self.check_node(v1)?;
+        self.check_node(v2)?;
+        self.edges.insert((*v1, *v2), weight);
+        Ok(())
+    }
+
+    fn contains(&self, node: &Ulid) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    fn linked(&self, node1: &Ulid, node2: &Ulid) -> bool {
+        self.edges.contains_key(&(*node1, *node2))
+    }
+
+    fn nodes<'a>(&'a self) -> impl Iterator<Item = (&'a Ulid, &'a N)>
+    where
+        N: 'a,
+    {
+        self.nodes.iter()
+    }
+
+    fn edges<'a>(&'a self) -> impl Iterator<Item = (&'a Ulid, &'a Ulid, &'a W)>
+    where
+        W: 'a,
+    {
+        self.edges.iter().map(|((from, to), w)| (from, to, w))
+    }
+
+    fn out_edges<'a>(&'a self, node: &Ulid) -> impl Iterator<Item = (&'a Ulid, &'a W)>
+    where
+        W: 'a,
+    {
+        let node = *node;
+        self.edges
+            .iter()
+            .filter(move |((from, _), _)| *from == node)
+            .map(|((_, to), w)| (to, w))
+    }
+
+    fn in_edges<'a>(&'a self, node: &Ulid) -> impl Iterator<Item = (&'a Ulid, &'a W)>
+    where
+        W: 'a,
+    {
+        let node = *node;
+        self.edges
+            .iter()
+            .filter(move |((_, to), _)| *to == node)
+            .map(|((from, _), w)| (from, w))
+    }
+
+    fn dijkstra(&self, start: &Ulid, end: &Ulid) -> Result<Option<(Vec<Ulid>, W)>> {
+        self.check_node(start)?;
+        self.check_node(end)?;
+
+        // Looking up the edges of a node in the map would go through all of them
+        let csr = Csr::from_graph(self);
+        let mut frontier: BinaryHeap<NodeState<W>> = BinaryHeap::new();
+        let mut from: HashMap<Ulid, Ulid> = HashMap::new();
+        let mut cost_so_far: HashMap<Ulid, W> = HashMap::new();
+
+        // Add the first node in the frontier, with a weight of 0
+        frontier.push(NodeState {
+            cost: W::default(),
+            node: *start,
+        });
+        cost_so_far.insert(*start, W::default());
+
+        // Try to get the next node in the frontier
+        while let Some(NodeState { cost, node }) = frontier.pop() {
+            // Path found !!!!
+            if node == *end {
+                let mut path = vec![*end];
+                let mut current = *end;
+
+                // Backtrack
+                while current != *start
+                    && let Some(previous) = from.get(&current)
+                {
+                    current = *previous;
+                    path.push(current);
+                }
+
+                // Reverse the order, so that it goes from start to end
+                path.reverse();
+
+                // Return value
+                return Ok(Some((path, cost)));
+            }
+
+            // This node was already reached with a smaller cost
+            if cost_so_far.get(&node).is_some_and(|c| *c < cost) {
+                continue;
+            }
+
+            let index = csr.index(&node).unwrap();
+            for (n, w) in csr.out_edges(index) {
+                let n = csr.id(n);
+                let new_cost = cost.clone() + w.clone();
+
+                // If we don't have a cost for this node, or the cost is higher than the new one, go through the current node
+                if cost_so_far.get(&n).is_none_or(|c| new_cost < *c) {
+                    cost_so_far.insert(n, new_cost.clone());
+                    from.insert(n, node);
+                    frontier.push(NodeState {
+                        cost: new_cost,
+                        node: n,
+                    });
+                }
+            }
+        }
+
+        // No path between the 2 exists :(
+        Ok(None)
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    /// Fails if the node isn't in the graph
+    pub fn check_node(&self, node: &Ulid) -> Result<()> {
+        if self.nodes.contains_key(node) {
+            Ok(())
+        } else {
+            Err(GraphError::NodeNotFound(*node))
+        }
+    }
+
+    /// Fails if one of the nodes isn't in the graph
+    pub fn check_nodes<'a>(&self, nodes: impl IntoIterator<Item = &'a Ulid>) -> Result<()> {
+        nodes.into_iter().try_for_each(|node| self.check_node(node))
+    }
+
+    /// Links both nodes in both directions, with the same weight
+    pub fn link_undirected(&mut self, node1: &Ulid, node2: &Ulid, weight: W) -> Result<()> {
+        self.check_node(node1)?;
+        self.check_node(node2)?;
+        self.connect(node1, node2, weight);
+        Ok(())
+    }
+
+    /// Links both nodes in both directions, without checking that they exist. Used by the
+    /// constructors, which only link the nodes they just inserted
+    pub(crate) fn connect(&mut self, node1: &Ulid, node2: &Ulid, weight: W) {
+        self.edges.insert((*node1, *node2), weight.clone());
+        self.edges.insert((*node2, *node1), weight);
+    }
+
+    /// Removes an edge along with its attributes, returning its weight
+    pub fn unlink(&mut self, edge: &(Ulid, Ulid)) -> Result<W> {
+        self.edge_attributes.remove(edge);
+        self.edges
+            .remove(edge)
+            .ok_or(GraphError::EdgeNotFound(edge.0, edge.1))
+    }
+
+    /// Names of the numeric attributes found on the edges
+    pub fn numeric_edge_attributes(&self) -> BTreeSet<String> {
+        self.edge_attributes
+            .values()
+            .flatten()
+            .filter(|(_, v)| v.as_number().is_some())
+            .map(|(k, _)| k.clone())
+            .collect()
+    }
+
+    /// Copy of the graph in which the edges are weighted by one of their numeric attributes,
+    /// converted by `convert`. Edges without this attribute keep their weight
+    ///
+    /// ```
+    /// use graphs::{AttributeValue, Graph, OrientedGraph, Real};
+    ///
+    /// let mut graph: OrientedGraph<()> = OrientedGraph::empty();
+    /// let (a, b) = (graph.insert(()), graph.insert(()));
+    /// graph.link(&a, &b, Real(1.0))?;
+    /// graph
+    ///     .edge_attributes
+    ///     .entry((a, b))
+    ///     .or_default()
+    ///     .insert("capacity".into(), AttributeValue::Number(12.5));
+    ///
+    /// let weighted = graph.weighted_by("capacity", Real);
+    /// assert_eq!(weighted.edges[&(a, b)], Real(12.5));
+    /// # Ok::<(), graphs::GraphError>(())
+    /// ```
+    pub fn weighted_by(&self, attribute: &str, convert: impl Fn(f64) -> W) -> Self
+    where
+        N: Clone,
+    {
+        let mut graph = self.clone();
+        for (edge, weight) in graph.edges.iter_mut() {
+            if let Some(value) = self
+                .edge_attributes
+                .get(edge)
+                .and_then(|a| a.get(attribute))
+                .and_then(|v| v.as_number())
+            {
+                *weight = convert(value);
+            }
+        }
+        graph
+    }
+
+    /// Builds the adjacency sets of the underlying simple undirected graph : edge directions
+    /// are ignored, as well as self-loops
+    pub fn undirected_adjacency(&self) -> HashMap<Ulid, HashSet<Ulid>> {
+        let mut adjacency: HashMap<Ulid, HashSet<Ulid>> =
+            self.nodes.keys().map(|id| (*id, HashSet::new())).collect();
+
+        for (from, to) in self.edges.keys() {
+            if from != to && adjacency.contains_key(from) && adjacency.contains_key(to) {
+                adjacency.get_mut(from).unwrap().insert(*to);
+                adjacency.get_mut(to).unwrap().insert(*from);
+            }
+        }
+
+        adjacency
+    }
+
+    /// Greedy coloring of the nodes, visited in the given order : each node gets the smallest
+    /// color, starting at 1, that none of its predecessors has. Returns the color of each node
+    pub fn greedy_coloring(&self, order: Vec<Ulid>) -> Result<HashMap<Ulid, u32>> {
+        self.greedy_coloring_with_progress(order, &|_| true)
+    }
+
+    pub fn greedy_coloring_with_progress(
+        &self,
+        order: Vec<Ulid>,
+        progress: &impl Progress,
+    ) -> Result<HashMap<Ulid, u32>> {
+        let unique: HashSet<&Ulid> = order.iter().collect();
+        if order.len() != self.node_count()
+            || unique.len() != order.len()
+            || !unique.iter().all(|id| self.nodes.contains_key(id))
+        {
+            return Err(GraphError::InvalidOrder);
+        }
+
+        let mut predecessors: HashMap<Ulid, Vec<Ulid>> = HashMap::new();
+        for (from, to) in self.edges.keys() {
+            predecessors.entry(*to).or_default().push(*from);
+        }
+        let steps = Steps::new(progress, order.len());
+        let mut colors: HashMap<Ulid, u32> = HashMap::new();
+
+        for i in order {
+            if !steps.step() {
+                break;
+            }
+            let mut neighbor_colors: Vec<&u32> = predecessors
+                .get(&i)
+                .into_iter()
+                .flatten()
+                .filter_map(|n| colors.get(n))
+                .collect();
+            neighbor_colors.sort();
+
+            let mut col = 1;
+            for c in neighbor_colors {
+                if *c == col {
+                    col += 1;
+                }
+            }
+
+            colors.insert(i, col);
+        }
+        steps.finish(colors)
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Spatial,
+    W: GraphWeight,
+{
+    /// Rectangle containing all the nodes, with some margin
+    pub fn bounding_rect(&self) -> Rect {
+        if self.node_count() == 0 {
+            return Rect::ZERO;
+        }
+
+        let mut min = pos2(f32::INFINITY, f32::INFINITY);
+        let mut max = pos2(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for (_, v) in self.nodes.iter() {
+            min = min.min(v.pos());
+            max = max.max(v.pos());
+        }
+        min.x -= POINT_RADIUS * 2.0;
+        min.y -= POINT_RADIUS * 2.0;
+        max.x += POINT_RADIUS * 2.0;
+        max.y += POINT_RADIUS * 2.0;
+        Rect { min, max }
+    }
+}
@@ -0,0 +1,40 @@
+//! Iteration helpers running on all cores with the `parallel` feature, and sequentially
+//! otherwise. Results never depend on the number of threads, except for the rounding of
+//! floating point sums
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Maps `f` over `0..n`, keeping the order of the results
+pub(crate) fn map_range<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return (0..n).into_par_iter().map(f).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    return (0..n).map(f).collect();
+}
+
+/// Folds `0..n` into accumulators created by `init`, which are then combined with `merge`
+pub(crate) fn fold_range<T, I, F, M>(n: usize, init: I, fold: F, merge: M) -> T
+where
+    T: Send,
+    I: Fn() -> T + Sync + Send,
+    F: Fn(T, usize) -> T + Sync + Send,
+    M: Fn(T, T) -> T + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return (0..n)
+        .into_par_iter()
+        .fold(&init, fold)
+        .reduce(&init, merge);
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        let _ = merge;
+        (0..n).fold(init(), fold)
+    }
+}
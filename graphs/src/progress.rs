@@ -0,0 +1,78 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{GraphError, Result};
+
+/// Followed by the long-running algorithms, which report the fraction of their work done,
+/// between 0 and 1. As soon as it returns false, they stop and fail with
+/// [`GraphError::Cancelled`]
+///
+/// ```
+/// use graphs::{GraphError, OrientedGraph, emath::Pos2};
+///
+/// let graph: OrientedGraph<Pos2> = OrientedGraph::complete(30);
+/// let cancelled = graph.maximal_cliques_with_progress(&|_| false);
+/// assert_eq!(cancelled, Err(GraphError::Cancelled));
+///
+/// let done = std::sync::Mutex::new(Vec::new());
+/// let cliques = graph.maximal_cliques_with_progress(&|fraction| {
+///     done.lock().unwrap().push(fraction);
+///     true
+/// })?;
+/// assert_eq!(cliques.len(), 1);
+/// assert!(done.into_inner().unwrap().is_sorted());
+/// # Ok::<(), GraphError>(())
+/// ```
+pub trait Progress: Sync {
+    fn report(&self, done: f32) -> bool;
+}
+
+impl<F> Progress for F
+where
+    F: Fn(f32) -> bool + Sync,
+{
+    fn report(&self, done: f32) -> bool {
+        self(done)
+    }
+}
+
+/// Counts the steps of an algorithm, possibly taken on several threads, reporting them to a
+/// [`Progress`] and remembering whether it asked to stop
+pub(crate) struct Steps<'a> {
+    progress: &'a dyn Progress,
+    total: usize,
+    done: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl<'a> Steps<'a> {
+    pub fn new(progress: &'a dyn Progress, total: usize) -> Self {
+        Self {
+            progress,
+            total,
+            done: AtomicUsize::new(0),
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Counts one more step, returning whether the algorithm should go on
+    pub fn step(&self) -> bool {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.progress.report(done as f32 / self.total.max(1) as f32) {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+        !self.is_cancelled()
+    }
+
+    /// The result of the algorithm, unless it was cancelled
+    pub fn finish<T>(&self, value: T) -> Result<T> {
+        if self.is_cancelled() {
+            Err(GraphError::Cancelled)
+        } else {
+            Ok(value)
+        }
+    }
+}
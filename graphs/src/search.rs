@@ -0,0 +1,349 @@
+use std::collections::{HashMap, VecDeque};
+
+use ulid::Ulid;
+
+use crate::{Csr, Graph, GraphError, GraphWeight, Result};
+
+/// Which edges a traversal follows
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Direction {
+    /// From their source to their target, reaching descendants
+    #[default]
+    Outgoing,
+    /// From their target to their source, reaching ancestors
+    Incoming,
+}
+
+/// Kind of an edge, relative to the forest built by a traversal
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EdgeKind {
+    /// Leads to a node discovered through it
+    Tree,
+    /// Leads back to an ancestor of its source, self-loops included
+    Back,
+    /// Leads to a descendant of its source, already discovered
+    Forward,
+    /// Leads to a node which is neither an ancestor nor a descendant
+    Cross,
+}
+
+/// Callbacks of a traversal, doing nothing by default. `()` can be used when they aren't
+/// needed
+pub trait Visitor {
+    /// The node is reached for the first time
+    fn discover(&mut self, _node: &Ulid, _depth: usize) {}
+    /// The edges of the node are about to be followed. In a depth-first traversal, this also
+    /// happens when coming back to it from one of its children
+    fn visit(&mut self, _node: &Ulid) {}
+    /// All the edges of the node have been followed
+    fn finish(&mut self, _node: &Ulid) {}
+    /// The edge is followed, in the direction of the traversal
+    fn edge(&mut self, _from: &Ulid, _to: &Ulid, _kind: EdgeKind) {}
+}
+
+impl Visitor for () {}
+
+/// Result of a traversal
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    /// Nodes, in the order they were discovered
+    pub order: Vec<Ulid>,
+    /// Depth of each node in the forest, the starts being at depth 0
+    pub depths: HashMap<Ulid, usize>,
+    /// Node each one was discovered from, the starts having none
+    pub parents: HashMap<Ulid, Ulid>,
+}
+
+/// State of a traversal over the dense indices of a [`Csr`]
+struct Forest<'a, W> {
+    csr: &'a Csr<W>,
+    /// Depth and parent of the discovered nodes
+    nodes: Vec<Option<(usize, Option<usize>)>>,
+    search: Search,
+}
+
+impl<'a, W: GraphWeight> Forest<'a, W> {
+    fn new(csr: &'a Csr<W>) -> Self {
+        Self {
+            csr,
+            nodes: vec![None; csr.node_count()],
+            search: Search::default(),
+        }
+    }
+
+    fn is_discovered(&self, node: usize) -> bool {
+        self.nodes[node].is_some()
+    }
+
+    fn discover(&mut self, node: usize, parent: Option<usize>, visitor: &mut impl Visitor) {
+        let depth = parent.and_then(|p| self.nodes[p]).map_or(0, |(d, _)| d + 1);
+        let id = self.csr.id(node);
+        visitor.discover(&id, depth);
+        self.nodes[node] = Some((depth, parent));
+        self.search.order.push(id);
+        self.search.depths.insert(id, depth);
+        if let Some(parent) = parent {
+            self.search.parents.insert(id, self.csr.id(parent));
+        }
+    }
+
+    /// Whether `ancestor` is on the path from the start of `node`'s tree to it
+    fn is_ancestor(&self, ancestor: usize, node: usize) -> bool {
+        let mut current = Some(node);
+        while let Some(node) = current {
+            if node == ancestor {
+                return true;
+            }
+            current = self.nodes[node].and_then(|(_, parent)| parent);
+        }
+        false
+    }
+
+    fn edge(&self, from: usize, to: usize, kind: EdgeKind, visitor: &mut impl Visitor) {
+        visitor.edge(&self.csr.id(from), &self.csr.id(to), kind);
+    }
+}
+
+/// Index of the graph following the edges in the direction of the traversal. Its nodes are
+/// numbered by ascending id, so that traversals are always the same
+fn adjacency<N, W: GraphWeight>(graph: &impl Graph<N, W>, direction: Direction) -> Csr<W> {
+    let csr = Csr::from_graph(graph);
+    match direction {
+        Direction::Outgoing => csr,
+        Direction::Incoming => csr.transpose(),
+    }
+}
+
+fn start_indices<W: GraphWeight>(
+    csr: &Csr<W>,
+    starts: impl IntoIterator<Item = Ulid>,
+) -> Result<Vec<usize>> {
+    starts
+        .into_iter()
+        .map(|s| csr.index(&s).ok_or(GraphError::NodeNotFound(s)))
+        .collect()
+}
+
+pub(crate) fn bfs<N, W: GraphWeight>(
+    graph: &impl Graph<N, W>,
+    starts: impl IntoIterator<Item = Ulid>,
+    direction: Direction,
+    visitor: &mut impl Visitor,
+) -> Result<Search> {
+    let csr = adjacency(graph, direction);
+    let starts = start_indices(&csr, starts)?;
+    Ok(csr_bfs(&csr, starts, visitor))
+}
+
+pub(crate) fn dfs<N, W: GraphWeight>(
+    graph: &impl Graph<N, W>,
+    starts: impl IntoIterator<Item = Ulid>,
+    direction: Direction,
+    visitor: &mut impl Visitor,
+) -> Result<Search> {
+    let csr = adjacency(graph, direction);
+    let starts = start_indices(&csr, starts)?;
+    Ok(csr_dfs(&csr, starts, visitor))
+}
+
+/// Breadth-first traversal following the edges of `csr`, from the given node indices
+pub(crate) fn csr_bfs<W: GraphWeight>(
+    csr: &Csr<W>,
+    starts: impl IntoIterator<Item = usize>,
+    visitor: &mut impl Visitor,
+) -> Search {
+    let mut forest = Forest::new(csr);
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if forest.is_discovered(start) {
+            continue;
+        }
+        forest.discover(start, None, visitor);
+        queue.push_back(start);
+
+        while let Some(node) = queue.pop_front() {
+            visitor.visit(&csr.id(node));
+            for &next in csr.successors(node) {
+                // Descendants are only discovered through tree edges, so that there are no
+                // forward edges
+                let kind = if !forest.is_discovered(next) {
+                    EdgeKind::Tree
+                } else if forest.is_ancestor(next, node) {
+                    EdgeKind::Back
+                } else {
+                    EdgeKind::Cross
+                };
+                forest.edge(node, next, kind, visitor);
+
+                if kind == EdgeKind::Tree {
+                    forest.discover(next, Some(node), visitor);
+                    queue.push_back(next);
+                }
+            }
+            visitor.finish(&csr.id(node));
+        }
+    }
+
+    forest.search
+}
+
+/// Depth-first traversal following the edges of `csr`, from the given node indices
+pub(crate) fn csr_dfs<W: GraphWeight>(
+    csr: &Csr<W>,
+    starts: impl IntoIterator<Item = usize>,
+    visitor: &mut impl Visitor,
+) -> Search {
+    let mut forest = Forest::new(csr);
+    // Position of each node in the discovery order, and whether it is finished
+    let mut discovered: Vec<Option<(usize, bool)>> = vec![None; csr.node_count()];
+
+    for start in starts {
+        if discovered[start].is_some() {
+            continue;
+        }
+        discovered[start] = Some((forest.search.order.len(), false));
+        forest.discover(start, None, visitor);
+        visitor.visit(&csr.id(start));
+
+        // Nodes being explored, along with the neighbors they still have to look at
+        let mut stack = vec![(start, csr.successors(start).iter())];
+
+        while let Some((node, remaining)) = stack.last_mut() {
+            let node = *node;
+            let Some(&next) = remaining.next() else {
+                stack.pop();
+                if let Some((_, finished)) = &mut discovered[node] {
+                    *finished = true;
+                }
+                visitor.finish(&csr.id(node));
+                // Back to the node this one was reached from
+                if let Some((parent, _)) = stack.last() {
+                    visitor.visit(&csr.id(*parent));
+                }
+                continue;
+            };
+
+            let position = discovered[node].map_or(0, |(p, _)| p);
+            let kind = match discovered[next] {
+                None => EdgeKind::Tree,
+                Some((_, false)) => EdgeKind::Back,
+                Some((index, true)) if index > position => EdgeKind::Forward,
+                Some(_) => EdgeKind::Cross,
+            };
+            forest.edge(node, next, kind, visitor);
+
+            if kind == EdgeKind::Tree {
+                discovered[next] = Some((forest.search.order.len(), false));
+                forest.discover(next, Some(node), visitor);
+                visitor.visit(&csr.id(next));
+                stack.push((next, csr.successors(next).iter()));
+            }
+        }
+    }
+
+    forest.search
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use emath::Pos2;
+    use ulid::Ulid;
+
+    use super::{Direction, EdgeKind, Search, Visitor};
+    use crate::{Graph, GraphError, OrientedGraph, Real};
+
+    type G = OrientedGraph<Pos2>;
+
+    /// Graph with `n` nodes, numbered by ascending id as traversals visit them, linked by the
+    /// given edges between their indices
+    fn graph(n: usize, edges: &[(usize, usize)]) -> (G, Vec<Ulid>) {
+        let mut graph = G::empty();
+        let mut ids: Vec<Ulid> = (0..n).map(|_| graph.insert(Pos2::ZERO)).collect();
+        ids.sort();
+        for (from, to) in edges {
+            graph.link(&ids[*from], &ids[*to], Real(1.0)).unwrap();
+        }
+        (graph, ids)
+    }
+
+    /// Records the kind of each followed edge
+    #[derive(Default)]
+    struct Kinds(HashMap<(Ulid, Ulid), EdgeKind>);
+
+    impl Visitor for Kinds {
+        fn edge(&mut self, from: &Ulid, to: &Ulid, kind: EdgeKind) {
+            self.0.insert((*from, *to), kind);
+        }
+    }
+
+    #[test]
+    fn dfs_edge_kinds() {
+        let (graph, ids) = graph(5, &[(0, 1), (1, 2), (2, 0), (0, 2), (0, 3), (3, 2)]);
+        let mut kinds = Kinds::default();
+        let search = graph
+            .dfs([ids[0]], Direction::Outgoing, &mut kinds)
+            .unwrap();
+
+        let kind = |from: usize, to: usize| kinds.0[&(ids[from], ids[to])];
+        assert_eq!(kind(0, 1), EdgeKind::Tree);
+        assert_eq!(kind(1, 2), EdgeKind::Tree);
+        assert_eq!(kind(2, 0), EdgeKind::Back);
+        // 2 was reached through 1 before following this edge
+        assert_eq!(kind(0, 2), EdgeKind::Forward);
+        assert_eq!(kind(0, 3), EdgeKind::Tree);
+        assert_eq!(kind(3, 2), EdgeKind::Cross);
+
+        assert_eq!(search.order, vec![ids[0], ids[1], ids[2], ids[3]]);
+        assert_eq!(search.depths[&ids[2]], 2);
+        assert_eq!(search.parents[&ids[3]], ids[0]);
+        // Not reachable from the start
+        assert!(!search.depths.contains_key(&ids[4]));
+    }
+
+    #[test]
+    fn bfs_depths_and_parents() {
+        let (graph, ids) = graph(7, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)]);
+        let depth = |search: &Search, i: usize| search.depths.get(&ids[i]).copied();
+
+        let mut kinds = Kinds::default();
+        let search = graph
+            .bfs([ids[0], ids[5]], Direction::Outgoing, &mut kinds)
+            .unwrap();
+        assert_eq!(
+            (0..7).map(|i| depth(&search, i)).collect::<Vec<_>>(),
+            vec![Some(0), Some(1), Some(1), Some(2), Some(3), Some(0), None]
+        );
+        assert_eq!(search.parents[&ids[3]], ids[1]);
+        assert_eq!(search.parents[&ids[4]], ids[3]);
+        assert!(!search.parents.contains_key(&ids[5]));
+        assert_eq!(kinds.0[&(ids[2], ids[3])], EdgeKind::Cross);
+        assert_eq!(kinds.0[&(ids[5], ids[4])], EdgeKind::Cross);
+
+        // The first start claims the nodes reachable from both
+        let search = graph
+            .bfs([ids[5], ids[0]], Direction::Outgoing, &mut ())
+            .unwrap();
+        assert_eq!(depth(&search, 4), Some(1));
+        assert_eq!(search.parents[&ids[4]], ids[5]);
+        assert_eq!(search.order.len(), 6);
+
+        let search = graph.bfs([ids[4]], Direction::Incoming, &mut ()).unwrap();
+        assert_eq!(
+            (0..7).map(|i| depth(&search, i)).collect::<Vec<_>>(),
+            vec![Some(3), Some(2), Some(2), Some(1), Some(0), Some(1), None]
+        );
+    }
+
+    #[test]
+    fn unknown_start() {
+        let (graph, _) = graph(2, &[(0, 1)]);
+        let missing = Ulid::new();
+        assert!(matches!(
+            graph.bfs([missing], Direction::Outgoing, &mut ()),
+            Err(GraphError::NodeNotFound(id)) if id == missing
+        ));
+    }
+}
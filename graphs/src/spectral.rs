@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use emath::pos2;
+use ulid::Ulid;
+
+use crate::{GraphError, GraphWeight, OrientedGraph, Progress, Result, Spatial};
+
+/// Maximum number of sweeps of the Jacobi eigenvalue algorithm
+const MAX_SWEEPS: usize = 100;
+/// Sum of the squares of the off-diagonal coefficients under which the matrix is diagonal
+const TOLERANCE: f64 = 1e-20;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MatrixKind {
+    #[default]
+    Adjacency,
+    Laplacian,
+    NormalizedLaplacian,
+}
+
+/// Dense square matrix, whose rows and columns follow the nodes of `order`
+#[derive(Debug, Clone)]
+pub struct GraphMatrix {
+    pub order: Vec<Ulid>,
+    pub values: Vec<Vec<f64>>,
+}
+
+/// Eigen decomposition of a symmetric matrix, sorted by ascending eigenvalue. `vectors[i]` is
+/// the unit eigenvector of `values[i]`, its components following the order of the matrix
+#[derive(Debug, Clone, Default)]
+pub struct Eigen {
+    pub order: Vec<Ulid>,
+    pub values: Vec<f64>,
+    pub vectors: Vec<Vec<f64>>,
+}
+
+impl GraphMatrix {
+    /// Computes the eigenvalues and eigenvectors of the matrix with the Jacobi eigenvalue
+    /// algorithm. The matrix must be symmetric
+    pub fn symmetric_eigen(&self) -> Eigen {
+        // Never cancelled
+        self.symmetric_eigen_with_progress(&|_| true)
+            .unwrap_or_default()
+    }
+
+    /// Eigen decomposition, the progress following the convergence of the off-diagonal
+    /// coefficients towards 0
+    pub fn symmetric_eigen_with_progress(&self, progress: &impl Progress) -> Result<Eigen> {
+        let n = self.order.len();
+        let mut a = self.values.clone();
+        let mut v: Vec<Vec<f64>> = (0..n)
+            .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+
+        let mut initial = None;
+        for _ in 0..MAX_SWEEPS {
+            let off_diagonal: f64 = (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .map(|(i, j)| a[i][j] * a[i][j])
+                .sum();
+            if off_diagonal < TOLERANCE {
+                break;
+            }
+            // The convergence being quadratic, it is measured on a logarithmic scale
+            let initial = *initial.get_or_insert(off_diagonal);
+            let done = ((initial / off_diagonal).ln() / (initial / TOLERANCE).ln()) as f32;
+
+            for p in 0..n {
+                if !progress.report(done) {
+                    return Err(GraphError::Cancelled);
+                }
+                for q in p + 1..n {
+                    if a[p][q].abs() < 1e-300 {
+                        continue;
+                    }
+
+                    // Rotation cancelling the (p, q) coefficient
+                    let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+
+                    for row in a.iter_mut().chain(v.iter_mut()) {
+                        let (kp, kq) = (row[p], row[q]);
+                        row[p] = c * kp - s * kq;
+                        row[q] = s * kp + c * kq;
+                    }
+                    let (upper, lower) = a.split_at_mut(q);
+                    for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                        (*pk, *qk) = (c * *pk - s * *qk, s * *pk + c * *qk);
+                    }
+                }
+            }
+        }
+
+        let mut indices: Vec<usize> = (0..n).collect();
+        indices.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+
+        Ok(Eigen {
+            order: self.order.clone(),
+            values: indices.iter().map(|i| a[*i][*i]).collect(),
+            vectors: indices
+                .iter()
+                .map(|i| v.iter().map(|row| row[*i]).collect())
+                .collect(),
+        })
+    }
+}
+
+/// The matrices are built from the underlying simple undirected graph (edge directions and
+/// self-loops are ignored), so that they are symmetric and their spectrum is real
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    pub fn matrix(&self, kind: MatrixKind) -> GraphMatrix {
+        match kind {
+            MatrixKind::Adjacency => self.adjacency_matrix(),
+            MatrixKind::Laplacian => self.laplacian_matrix(),
+            MatrixKind::NormalizedLaplacian => self.normalized_laplacian_matrix(),
+        }
+    }
+
+    /// `A[i][j]` is 1 if nodes i and j are linked, 0 otherwise
+    pub fn adjacency_matrix(&self) -> GraphMatrix {
+        let adjacency = self.undirected_adjacency();
+        let mut order: Vec<Ulid> = adjacency.keys().copied().collect();
+        order.sort();
+
+        let values = order
+            .iter()
+            .map(|i| {
+                order
+                    .iter()
+                    .map(|j| if adjacency[i].contains(j) { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+
+        GraphMatrix { order, values }
+    }
+
+    /// `L = D - A`, with `D` the diagonal matrix of degrees
+    pub fn laplacian_matrix(&self) -> GraphMatrix {
+        let mut matrix = self.adjacency_matrix();
+
+        for (i, row) in matrix.values.iter_mut().enumerate() {
+            let degree: f64 = row.iter().sum();
+            row.iter_mut().for_each(|a| *a = -*a);
+            row[i] = degree;
+        }
+
+        matrix
+    }
+
+    /// `L = I - D^-1/2 A D^-1/2`. Isolated nodes have a null row and column
+    pub fn normalized_laplacian_matrix(&self) -> GraphMatrix {
+        let mut matrix = self.adjacency_matrix();
+        let scale: Vec<f64> = matrix
+            .values
+            .iter()
+            .map(|row| {
+                let degree: f64 = row.iter().sum();
+                if degree > 0.0 {
+                    1.0 / degree.sqrt()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        for (i, row) in matrix.values.iter_mut().enumerate() {
+            for (j, a) in row.iter_mut().enumerate() {
+                *a = -*a * scale[i] * scale[j];
+            }
+            if scale[i] > 0.0 {
+                row[i] = 1.0;
+            }
+        }
+
+        matrix
+    }
+
+    pub fn spectrum(&self, kind: MatrixKind) -> Eigen {
+        self.matrix(kind).symmetric_eigen()
+    }
+
+    pub fn spectrum_with_progress(
+        &self,
+        kind: MatrixKind,
+        progress: &impl Progress,
+    ) -> Result<Eigen> {
+        self.matrix(kind).symmetric_eigen_with_progress(progress)
+    }
+
+    /// Second smallest eigenvalue of the Laplacian, which is non-zero if and only if the graph is
+    /// connected
+    pub fn algebraic_connectivity(&self) -> f64 {
+        self.spectrum(MatrixKind::Laplacian)
+            .values
+            .get(1)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Eigenvector of the algebraic connectivity, whose signs give a good split of the graph
+    pub fn fiedler_vector(&self) -> HashMap<Ulid, f64> {
+        let eigen = self.spectrum(MatrixKind::Laplacian);
+
+        match eigen.vectors.get(1) {
+            Some(vector) => eigen
+                .order
+                .into_iter()
+                .zip(vector.iter().copied())
+                .collect(),
+            None => eigen.order.into_iter().map(|id| (id, 0.0)).collect(),
+        }
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    N: Spatial,
+    W: GraphWeight,
+{
+    /// Places the nodes using the Fiedler vector as X axis, and the next eigenvector of the
+    /// Laplacian as Y axis, in a square of side `2 * scale` centered on the origin
+    pub fn spectral_layout(&mut self, scale: f32) {
+        // Never cancelled
+        let _ = self.spectral_layout_with_progress(scale, &|_| true);
+    }
+
+    /// Spectral layout, leaving the graph as it is if cancelled while computing the spectrum
+    pub fn spectral_layout_with_progress(
+        &mut self,
+        scale: f32,
+        progress: &impl Progress,
+    ) -> Result<()> {
+        let eigen = self.spectrum_with_progress(MatrixKind::Laplacian, progress)?;
+        let n = eigen.order.len();
+
+        let axis = |k: usize| -> Vec<f32> {
+            let vector = eigen
+                .vectors
+                .get(k)
+                .cloned()
+                .unwrap_or_else(|| vec![0.0; n]);
+            let max = vector.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+            vector
+                .iter()
+                .map(|x| {
+                    if max > 0.0 {
+                        (x / max) as f32 * scale
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        let (xs, ys) = (axis(1), axis(2));
+
+        for (i, id) in eigen.order.iter().enumerate() {
+            if let Some(node) = self.nodes.get_mut(id) {
+                node.set_pos(pos2(xs[i], ys[i]));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use emath::Pos2;
+
+    use super::{GraphMatrix, MatrixKind};
+    use crate::OrientedGraph;
+
+    type G = OrientedGraph<Pos2>;
+
+    fn assert_values(computed: &[f64], expected: impl IntoIterator<Item = f64>) {
+        let mut expected: Vec<f64> = expected.into_iter().collect();
+        expected.sort_by(f64::total_cmp);
+        assert_eq!(computed.len(), expected.len());
+        for (c, e) in computed.iter().zip(expected) {
+            assert!((c - e).abs() < 1e-9, "{computed:?} isn't {e}");
+        }
+    }
+
+    /// Checks that `M v = λ v` for every eigenpair
+    fn assert_eigenpairs(matrix: &GraphMatrix) {
+        let eigen = matrix.symmetric_eigen();
+        for (value, vector) in eigen.values.iter().zip(eigen.vectors.iter()) {
+            for (row, v) in matrix.values.iter().zip(vector) {
+                let product: f64 = row.iter().zip(vector).map(|(a, x)| a * x).sum();
+                assert!((product - value * v).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn complete_graph_spectrum() {
+        let graph = G::complete(6);
+        assert_values(
+            &graph.spectrum(MatrixKind::Adjacency).values,
+            std::iter::once(5.0).chain([-1.0; 5]),
+        );
+        assert_values(
+            &graph.spectrum(MatrixKind::Laplacian).values,
+            std::iter::once(0.0).chain([6.0; 5]),
+        );
+        assert_eigenpairs(&graph.adjacency_matrix());
+    }
+
+    #[test]
+    fn cycle_spectrum() {
+        let n = 7;
+        let graph = G::cycle(n);
+        assert_values(
+            &graph.spectrum(MatrixKind::Adjacency).values,
+            (0..n).map(|k| 2.0 * (2.0 * PI * k as f64 / n as f64).cos()),
+        );
+        // The cycle is 2-regular, so its normalized Laplacian is `I - A / 2`
+        assert_values(
+            &graph.spectrum(MatrixKind::NormalizedLaplacian).values,
+            (0..n).map(|k| 1.0 - (2.0 * PI * k as f64 / n as f64).cos()),
+        );
+        assert_eigenpairs(&graph.laplacian_matrix());
+    }
+
+    #[test]
+    fn petersen_spectrum() {
+        let graph = G::petersen();
+        assert_values(
+            &graph.spectrum(MatrixKind::Adjacency).values,
+            [3.0].into_iter().chain([1.0; 5]).chain([-2.0; 4]),
+        );
+        assert!((graph.algebraic_connectivity() - 2.0).abs() < 1e-9);
+        assert_eigenpairs(&graph.normalized_laplacian_matrix());
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use ulid::Ulid;
+
+use crate::{
+    Csr, EdgeKind, GraphError, GraphWeight, OrientedGraph, Result, Visitor,
+    search::{csr_bfs, csr_dfs},
+};
+
+/// Traversal algorithms which can record a trace of their execution
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Traversal {
+    #[default]
+    BreadthFirst,
+    DepthFirst,
+    Dijkstra,
+    /// Prim's minimum spanning tree, ignoring the direction of the edges
+    Prim,
+}
+
+impl Traversal {
+    pub const ALL: [Traversal; 4] = [
+        Traversal::BreadthFirst,
+        Traversal::DepthFirst,
+        Traversal::Dijkstra,
+        Traversal::Prim,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Traversal::BreadthFirst => "BFS",
+            Traversal::DepthFirst => "DFS",
+            Traversal::Dijkstra => "Dijkstra",
+            Traversal::Prim => "Prim",
+        }
+    }
+
+    /// Whether the traversal follows the edges both ways
+    pub fn is_undirected(&self) -> bool {
+        matches!(self, Traversal::Prim)
+    }
+}
+
+/// Step of the execution of a traversal
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TraceEvent {
+    /// The node enters the frontier
+    Discover(Ulid),
+    /// The node becomes the one being processed
+    Visit(Ulid),
+    /// The node leaves the frontier, it won't be processed again
+    Finish(Ulid),
+    /// The edge is being looked at
+    Examine(Ulid, Ulid),
+    /// The node is now reached through `parent`, replacing its previous parent
+    Parent { node: Ulid, parent: Ulid },
+}
+
+/// State of a traversal after some of its events
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TraceState {
+    pub frontier: HashSet<Ulid>,
+    pub finished: HashSet<Ulid>,
+    pub current: Option<Ulid>,
+    pub edge: Option<(Ulid, Ulid)>,
+    /// Parent of each reached node, forming the tree built by the traversal
+    pub parents: HashMap<Ulid, Ulid>,
+}
+
+impl TraceState {
+    /// State after all the given events
+    ///
+    /// ```
+    /// use graphs::{Graph, OrientedGraph, Real, TraceState, Traversal};
+    ///
+    /// let mut graph: OrientedGraph<()> = OrientedGraph::empty();
+    /// let [a, b, c] = [(), (), ()].map(|n| graph.insert(n));
+    /// graph.link(&a, &b, Real(1.0))?;
+    /// graph.link(&a, &c, Real(1.0))?;
+    ///
+    /// let trace = graph.trace(Traversal::BreadthFirst, &a)?;
+    /// // While `a` is processed, both its neighbors join it in the frontier
+    /// let state = TraceState::replay(&trace[..8]);
+    /// assert_eq!(state.current, Some(a));
+    /// assert_eq!(state.frontier.len(), 3);
+    ///
+    /// let state = TraceState::replay(&trace);
+    /// assert_eq!(state.finished.len(), 3);
+    /// assert_eq!(state.parents[&c], a);
+    /// # Ok::<(), graphs::GraphError>(())
+    /// ```
+    pub fn replay<'a>(events: impl IntoIterator<Item = &'a TraceEvent>) -> Self {
+        let mut state = Self::default();
+        for event in events {
+            state.apply(event);
+        }
+        state
+    }
+
+    pub fn apply(&mut self, event: &TraceEvent) {
+        match *event {
+            TraceEvent::Discover(node) => {
+                self.frontier.insert(node);
+            }
+            TraceEvent::Visit(node) => {
+                self.current = Some(node);
+                self.edge = None;
+            }
+            TraceEvent::Finish(node) => {
+                self.frontier.remove(&node);
+                self.finished.insert(node);
+                self.edge = None;
+            }
+            TraceEvent::Examine(from, to) => self.edge = Some((from, to)),
+            TraceEvent::Parent { node, parent } => {
+                self.parents.insert(node, parent);
+            }
+        }
+    }
+}
+
+/// Records the steps of a breadth-first or depth-first traversal
+#[derive(Debug, Default)]
+struct Tracer {
+    trace: Vec<TraceEvent>,
+}
+
+impl Visitor for Tracer {
+    fn discover(&mut self, node: &Ulid, _depth: usize) {
+        self.trace.push(TraceEvent::Discover(*node));
+    }
+
+    fn visit(&mut self, node: &Ulid) {
+        self.trace.push(TraceEvent::Visit(*node));
+    }
+
+    fn finish(&mut self, node: &Ulid) {
+        self.trace.push(TraceEvent::Finish(*node));
+    }
+
+    fn edge(&mut self, from: &Ulid, to: &Ulid, kind: EdgeKind) {
+        self.trace.push(TraceEvent::Examine(*from, *to));
+        if kind == EdgeKind::Tree {
+            self.trace.push(TraceEvent::Parent {
+                node: *to,
+                parent: *from,
+            });
+        }
+    }
+}
+
+impl<N, W> OrientedGraph<N, W>
+where
+    W: GraphWeight,
+{
+    /// Runs a traversal from `start`, recording every step of its execution. Neighbors are
+    /// looked at by ascending id, so that the trace is always the same
+    pub fn trace(&self, traversal: Traversal, start: &Ulid) -> Result<Vec<TraceEvent>> {
+        let csr = Csr::from_graph(self);
+        let start = csr.index(start).ok_or(GraphError::NodeNotFound(*start))?;
+
+        Ok(match traversal {
+            Traversal::BreadthFirst => {
+                let mut tracer = Tracer::default();
+                csr_bfs(&csr, [start], &mut tracer);
+                tracer.trace
+            }
+            Traversal::DepthFirst => {
+                let mut tracer = Tracer::default();
+                csr_dfs(&csr, [start], &mut tracer);
+                tracer.trace
+            }
+            Traversal::Dijkstra => trace_dijkstra(&csr, start),
+            Traversal::Prim => trace_prim(&csr, start),
+        })
+    }
+}
+
+fn trace_dijkstra<W: GraphWeight>(csr: &Csr<W>, start: usize) -> Vec<TraceEvent> {
+    let mut trace = vec![TraceEvent::Discover(csr.id(start))];
+    let mut costs: Vec<Option<W>> = vec![None; csr.node_count()];
+    let mut finished = vec![false; csr.node_count()];
+    let mut frontier = BinaryHeap::from([Reverse((W::default(), start))]);
+    costs[start] = Some(W::default());
+
+    while let Some(Reverse((cost, node))) = frontier.pop() {
+        if std::mem::replace(&mut finished[node], true) {
+            continue;
+        }
+
+        let id = csr.id(node);
+        trace.push(TraceEvent::Visit(id));
+        for (next, w) in csr.out_edges(node) {
+            trace.push(TraceEvent::Examine(id, csr.id(next)));
+            let new_cost = cost.clone() + w.clone();
+            if !finished[next] && costs[next].as_ref().is_none_or(|c| new_cost < *c) {
+                costs[next] = Some(new_cost.clone());
+                trace.push(TraceEvent::Parent {
+                    node: csr.id(next),
+                    parent: id,
+                });
+                trace.push(TraceEvent::Discover(csr.id(next)));
+                frontier.push(Reverse((new_cost, next)));
+            }
+        }
+        trace.push(TraceEvent::Finish(id));
+    }
+
+    trace
+}
+
+fn trace_prim<W: GraphWeight>(csr: &Csr<W>, start: usize) -> Vec<TraceEvent> {
+    let transpose = csr.transpose();
+    let mut trace = vec![TraceEvent::Discover(csr.id(start))];
+    // Lightest known edge linking each node to the tree
+    let mut keys: Vec<Option<W>> = vec![None; csr.node_count()];
+    let mut in_tree = vec![false; csr.node_count()];
+    let mut frontier = BinaryHeap::from([Reverse((W::default(), start))]);
+    keys[start] = Some(W::default());
+
+    while let Some(Reverse((key, node))) = frontier.pop() {
+        if keys[node].as_ref().is_some_and(|k| *k < key)
+            || std::mem::replace(&mut in_tree[node], true)
+        {
+            continue;
+        }
+
+        let id = csr.id(node);
+        trace.push(TraceEvent::Visit(id));
+        let mut edges: Vec<(usize, W)> = csr
+            .out_edges(node)
+            .chain(transpose.out_edges(node))
+            .map(|(next, w)| (next, w.clone()))
+            .collect();
+        // Keep the lightest edge towards each neighbor
+        edges.sort();
+        edges.dedup_by_key(|(next, _)| *next);
+
+        for (next, w) in edges {
+            trace.push(TraceEvent::Examine(id, csr.id(next)));
+            if !in_tree[next] && keys[next].as_ref().is_none_or(|k| w < *k) {
+                keys[next] = Some(w.clone());
+                trace.push(TraceEvent::Parent {
+                    node: csr.id(next),
+                    parent: id,
+                });
+                trace.push(TraceEvent::Discover(csr.id(next)));
+                frontier.push(Reverse((w, next)));
+            }
+        }
+        trace.push(TraceEvent::Finish(id));
+    }
+
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use emath::Pos2;
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use ulid::Ulid;
+
+    use super::{TraceState, Traversal};
+    use crate::{Csr, Direction, Graph, OrientedGraph, Real};
+
+    type G = OrientedGraph<Pos2>;
+
+    /// Directed graph with integer weights, in which the first node reaches all the others
+    fn weighted(seed: u64) -> (G, Vec<Ulid>) {
+        let rng = &mut StdRng::seed_from_u64(seed);
+        let mut graph = G::empty();
+        let ids: Vec<Ulid> = (0..12).map(|_| graph.insert(Pos2::ZERO)).collect();
+        let weight = |rng: &mut StdRng| Real(rng.random_range(1..10) as f64);
+
+        for pair in ids.windows(2) {
+            graph.link(&pair[0], &pair[1], weight(rng)).unwrap();
+        }
+        for _ in 0..20 {
+            let (a, b) = (ids[rng.random_range(0..12)], ids[rng.random_range(0..12)]);
+            if a != b {
+                graph.link(&a, &b, weight(rng)).unwrap();
+            }
+        }
+        (graph, ids)
+    }
+
+    /// Weight of a minimum spanning tree of the underlying undirected graph, with Kruskal's
+    /// algorithm
+    fn spanning_tree_weight(graph: &G) -> f64 {
+        let mut edges: Vec<(f64, Ulid, Ulid)> = graph
+            .edges
+            .iter()
+            .map(|((a, b), w)| (w.0, *a, *b))
+            .collect();
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut components: HashMap<Ulid, Ulid> = graph.nodes.keys().map(|id| (*id, *id)).collect();
+        let root = |components: &HashMap<Ulid, Ulid>, mut id: Ulid| {
+            while components[&id] != id {
+                id = components[&id];
+            }
+            id
+        };
+
+        let mut total = 0.0;
+        for (w, a, b) in edges {
+            let (a, b) = (root(&components, a), root(&components, b));
+            if a != b {
+                components.insert(a, b);
+                total += w;
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn traversals_build_the_search_trees() {
+        let (graph, ids) = weighted(1);
+        let all: HashSet<Ulid> = ids.iter().copied().collect();
+
+        let state = TraceState::replay(&graph.trace(Traversal::BreadthFirst, &ids[0]).unwrap());
+        let search = graph.bfs([ids[0]], Direction::Outgoing, &mut ()).unwrap();
+        assert_eq!(state.finished, all);
+        assert_eq!(state.parents, search.parents);
+
+        let state = TraceState::replay(&graph.trace(Traversal::DepthFirst, &ids[0]).unwrap());
+        let search = graph.dfs([ids[0]], Direction::Outgoing, &mut ()).unwrap();
+        assert_eq!(state.finished, all);
+        assert_eq!(state.parents, search.parents);
+        assert!(state.frontier.is_empty());
+    }
+
+    #[test]
+    fn dijkstra_builds_a_shortest_path_tree() {
+        for seed in 0..10 {
+            let (graph, ids) = weighted(seed);
+            let csr = Csr::from_graph(&graph);
+            let distances = csr.dijkstra(csr.index(&ids[5]).unwrap());
+            let distance = |id: &Ulid| distances[csr.index(id).unwrap()];
+
+            let state = TraceState::replay(&graph.trace(Traversal::Dijkstra, &ids[5]).unwrap());
+            let reachable: HashSet<Ulid> = ids
+                .iter()
+                .filter(|id| distance(id).is_some())
+                .copied()
+                .collect();
+            assert_eq!(state.finished, reachable);
+            assert_eq!(state.parents.len(), reachable.len() - 1);
+            for (node, parent) in state.parents.iter() {
+                let through_parent = distance(parent).unwrap() + graph.edges[&(*parent, *node)];
+                assert_eq!(distance(node), Some(through_parent));
+            }
+        }
+    }
+
+    #[test]
+    fn prim_builds_a_minimum_spanning_tree() {
+        for seed in 0..10 {
+            let (graph, ids) = weighted(seed);
+            // Started from the last node, which no edge may leave
+            let state = TraceState::replay(&graph.trace(Traversal::Prim, &ids[11]).unwrap());
+            assert_eq!(state.finished.len(), ids.len());
+
+            let lightest = |a: Ulid, b: Ulid| {
+                [(a, b), (b, a)]
+                    .iter()
+                    .filter_map(|e| graph.edges.get(e))
+                    .map(|w| w.0)
+                    .fold(f64::INFINITY, f64::min)
+            };
+            let weight: f64 = state.parents.iter().map(|(n, p)| lightest(*n, *p)).sum();
+            assert_eq!(state.parents.len(), ids.len() - 1);
+            assert_eq!(weight, spanning_tree_weight(&graph));
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Sub},
+};
+
+use emath::Numeric;
+
+/// Floating-point weight with a total order, so that it can be used as a [`crate::GraphWeight`].
+/// NaN is equal to itself and greater than every other value, so that a path going through a
+/// NaN weight is never preferred to one that doesn't
+///
+/// ```
+/// use graphs::Real;
+///
+/// assert!(Real(f64::NAN) > Real::INFINITY);
+/// assert_eq!(Real(0.1) + Real(0.2), Real(0.1 + 0.2));
+/// // The precision rounds the number, without trailing zeros
+/// assert_eq!(format!("{:.2}", Real(1.5)), "1.5");
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Real(pub f64);
+
+impl Real {
+    pub const INFINITY: Real = Real(f64::INFINITY);
+}
+
+impl Ord for Real {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0.is_nan(), other.0.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            // Both are numbers, so they can be compared
+            (false, false) => self.0.partial_cmp(&other.0).unwrap(),
+        }
+    }
+}
+
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Real {}
+
+impl Add for Real {
+    type Output = Real;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Real(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Real {
+    type Output = Real;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Real(self.0 - rhs.0)
+    }
+}
+
+impl From<f64> for Real {
+    fn from(value: f64) -> Self {
+        Real(value)
+    }
+}
+
+impl From<i32> for Real {
+    fn from(value: i32) -> Self {
+        Real(value.into())
+    }
+}
+
+impl Display for Real {
+    /// Shortest representation of the number, rounded to the precision if one is given
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) if self.0.is_finite() => {
+                let text = format!("{:.*}", precision, self.0);
+                let text = if text.contains('.') {
+                    text.trim_end_matches('0').trim_end_matches('.')
+                } else {
+                    &text
+                };
+                f.write_str(text)
+            }
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+// Allows editing weights with `DragValue`
+impl Numeric for Real {
+    const INTEGRAL: bool = false;
+    const MIN: Self = Real(f64::MIN);
+    const MAX: Self = Real(f64::MAX);
+
+    fn to_f64(self) -> f64 {
+        self.0
+    }
+
+    fn from_f64(num: f64) -> Self {
+        Real(num)
+    }
+}
@@ -1,5 +0,0 @@
-pub mod context_menu;
-pub mod inspector;
-pub mod misc;
-pub mod plot;
-pub mod widgets;
\ No newline at end of file